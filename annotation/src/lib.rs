@@ -0,0 +1,181 @@
+//! The `#[typeshare]` attribute, which marks structs and enums for typeshare to export.
+//!
+//! The attribute leaves the type as it is, but checks its arguments and the types of its fields
+//! when the crate is built, so mistakes fail `cargo build` rather than code generation.
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, AttributeArgs, Error, Fields, Item, Lit, Meta, NestedMeta, Type};
+
+/// Arguments accepted on types and fields, as read by typeshare.
+const ARGUMENTS: &[&str] = &["serialized_as", "ts", "swift", "java"];
+
+/// Marks a struct or enum for typeshare to export.
+///
+/// On the type and on its fields, `serialized_as = "..."` names the Rust type it's serialized as,
+/// for hand-written `Serialize` impls, and `ts`, `swift` and `java` give the type to write in
+/// that language.
+///
+/// ```ignore
+/// #[typeshare(serialized_as = "String")]
+/// pub struct Secret(Vec<u8>);
+///
+/// #[typeshare]
+/// pub struct Login {
+///     #[typeshare(ts = "`${number}px`", swift = "String", java = "String")]
+///     pub width: Width,
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn typeshare(args: TokenStream, item: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(args as AttributeArgs);
+    let mut item = parse_macro_input!(item as Item);
+
+    let mut errors = Vec::new();
+    let serialized_as = check_arguments(&args, &mut errors);
+    match &mut item {
+        // A type serialized as another needn't have fields typeshare can map.
+        Item::Struct(s) => check_fields(&mut s.fields, serialized_as.is_none(), &mut errors),
+        Item::Enum(e) => {
+            for v in e.variants.iter_mut() {
+                check_fields(&mut v.fields, serialized_as.is_none(), &mut errors);
+            }
+        }
+        item => errors.push(Error::new(item.span(), "#[typeshare] only applies to structs and enums")),
+    }
+
+    let errors = errors.iter().map(Error::to_compile_error);
+    quote!(#item #(#errors)*).into()
+}
+
+/// Checks each argument is a known name with a string value, given once. Returns the type from
+/// `serialized_as`, after checking it can be mapped.
+fn check_arguments(args: &[NestedMeta], errors: &mut Vec<Error>) -> Option<Type> {
+    let mut seen = Vec::new();
+    let mut serialized_as = None;
+    for arg in args {
+        let (name, lit) = match arg {
+            NestedMeta::Meta(Meta::NameValue(nv)) => (nv.ident.to_string(), &nv.lit),
+            _ => {
+                errors.push(Error::new(arg.span(), format!("expected `name = \"...\"` with one of: {}", ARGUMENTS.join(", "))));
+                continue;
+            }
+        };
+        if !ARGUMENTS.contains(&name.as_str()) {
+            errors.push(Error::new(arg.span(), format!("unknown argument `{}`, expected one of: {}", name, ARGUMENTS.join(", "))));
+            continue;
+        }
+        if seen.contains(&name) {
+            errors.push(Error::new(arg.span(), format!("`{}` is given more than once", name)));
+            continue;
+        }
+        seen.push(name.clone());
+
+        let value = match lit {
+            Lit::Str(value) => value,
+            _ => {
+                errors.push(Error::new(lit.span(), format!("`{}` takes a string", name)));
+                continue;
+            }
+        };
+        if name == "serialized_as" {
+            match value.parse::<Type>() {
+                Ok(ty) => {
+                    check_type(&ty, errors);
+                    serialized_as = Some(ty);
+                }
+                Err(e) => errors.push(Error::new(value.span(), format!("`serialized_as` must be a Rust type: {}", e))),
+            }
+        }
+    }
+    serialized_as
+}
+
+/// Checks the `#[typeshare(...)]` arguments on fields and removes them, since only the attribute
+/// on the type is a macro. With `check_types`, also checks each exported field's type can be mapped.
+fn check_fields(fields: &mut Fields, check_types: bool, errors: &mut Vec<Error>) {
+    for field in fields.iter_mut() {
+        let mut args = Vec::new();
+        for attr in field.attrs.iter().filter(|a| a.path.is_ident("typeshare")) {
+            match attr.parse_meta() {
+                Ok(Meta::List(list)) => args.extend(list.nested),
+                Ok(Meta::Word(_)) => {}
+                Ok(meta) => errors.push(Error::new(meta.span(), "expected `#[typeshare(name = \"...\")]`")),
+                Err(e) => errors.push(e),
+            }
+        }
+        field.attrs.retain(|a| !a.path.is_ident("typeshare"));
+
+        let serialized_as = check_arguments(&args, errors);
+        let overrides_every_language = ["ts", "swift", "java"]
+            .iter()
+            .all(|language| args.iter().any(|arg| matches!(arg, NestedMeta::Meta(Meta::NameValue(nv)) if nv.ident == language)));
+        if check_types && serialized_as.is_none() && !overrides_every_language && !is_skipped(&field.attrs) {
+            check_type(&field.ty, errors);
+        }
+    }
+}
+
+/// Fields with `#[serde(skip)]`, or both `skip_serializing` and `skip_deserializing`, aren't exported.
+fn is_skipped(attrs: &[syn::Attribute]) -> bool {
+    let mut words = Vec::new();
+    for attr in attrs.iter().filter(|a| a.path.is_ident("serde")) {
+        if let Ok(Meta::List(list)) = attr.parse_meta() {
+            for arg in list.nested.iter() {
+                if let NestedMeta::Meta(Meta::Word(word)) = arg {
+                    words.push(word.to_string());
+                }
+            }
+        }
+    }
+    let has = |name: &str| words.iter().any(|word| word == name);
+    has("skip") || (has("skip_serializing") && has("skip_deserializing"))
+}
+
+/// Reports the parts of `ty` that have no JSON form for typeshare to write in other languages.
+fn check_type(ty: &Type, errors: &mut Vec<Error>) {
+    let kind = match ty {
+        Type::Slice(slice) => return check_type(&slice.elem, errors),
+        Type::Array(array) => return check_type(&array.elem, errors),
+        Type::Reference(reference) => return check_type(&reference.elem, errors),
+        Type::Paren(paren) => return check_type(&paren.elem, errors),
+        Type::Group(group) => return check_type(&group.elem, errors),
+        Type::Tuple(tuple) => {
+            for elem in tuple.elems.iter() {
+                check_type(elem, errors);
+            }
+            return;
+        }
+        Type::Path(path) if path.qself.is_some() => "qualified paths such as `<T as Trait>::Output`",
+        Type::Path(path) => {
+            for segment in path.path.segments.iter() {
+                if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
+                    for arg in args.args.iter() {
+                        if let syn::GenericArgument::Type(ty) = arg {
+                            check_type(ty, errors);
+                        }
+                    }
+                }
+            }
+            return;
+        }
+        Type::BareFn(_) => "function pointers",
+        Type::TraitObject(_) => "trait objects",
+        Type::ImplTrait(_) => "`impl Trait` types",
+        Type::Ptr(_) => "raw pointers",
+        Type::Never(_) => "the never type",
+        Type::Macro(_) => "macros in type position",
+        Type::Infer(_) => "inferred types",
+        Type::Verbatim(_) => "this type",
+    };
+    errors.push(Error::new(
+        ty.span(),
+        format!(
+            "typeshare can't map {} to other languages, use #[typeshare(serialized_as = \"...\")] to give the type it's serialized as",
+            kind
+        ),
+    ));
+}
@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+use typeshare_annotation::typeshare;
+
+#[typeshare(serialized_as = "String")]
+pub struct Secret {
+    pub bytes: Vec<u8>,
+    pub on_drop: fn(),
+}
+
+#[typeshare]
+pub struct Login {
+    pub password: Secret,
+    pub by_site: HashMap<String, Option<Vec<(u32, &'static str)>>>,
+    #[typeshare(serialized_as = "String")]
+    pub callback: fn() -> u32,
+    #[typeshare(ts = "number", swift = "Int", java = "int")]
+    pub raw: *const u8,
+}
+
+#[typeshare]
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct Session {
+    pub id: String,
+    #[serde(skip_serializing, skip_deserializing)]
+    pub on_expiry: Option<fn()>,
+}
+
+#[typeshare(ts = "string", swift = "String", java = "String")]
+pub enum Token {
+    Bearer(String),
+    Basic {
+        #[typeshare(serialized_as = "String")]
+        user: Secret,
+    },
+}
+
+#[test]
+fn keeps_the_item_and_strips_field_arguments() {
+    let login = Login {
+        password: Secret {
+            bytes: vec![1, 2],
+            on_drop: || {},
+        },
+        by_site: HashMap::new(),
+        callback: || 1,
+        raw: std::ptr::null(),
+    };
+    assert_eq!(login.password.bytes, [1, 2]);
+    assert_eq!((login.callback)(), 1);
+    (login.password.on_drop)();
+
+    match (Token::Bearer("t".to_string()), Token::Basic { user: login.password }) {
+        (Token::Bearer(token), Token::Basic { user }) => assert_eq!((token.as_str(), user.bytes.len()), ("t", 2)),
+        _ => unreachable!(),
+    }
+
+    let session = Session {
+        id: "s".to_string(),
+        on_expiry: None,
+    };
+    assert!(session.on_expiry.is_none());
+}
+
+#[test]
+fn rejects_bad_arguments_and_unmappable_fields() {
+    trybuild::TestCases::new().compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,9 @@
+use typeshare_annotation::typeshare;
+
+#[typeshare]
+pub struct Login {
+    #[typeshare(ts = "string", ts = "number")]
+    pub width: u32,
+}
+
+fn main() {}
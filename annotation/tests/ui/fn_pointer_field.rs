@@ -0,0 +1,8 @@
+use typeshare_annotation::typeshare;
+
+#[typeshare]
+pub struct Login {
+    pub callback: Option<fn() -> u32>,
+}
+
+fn main() {}
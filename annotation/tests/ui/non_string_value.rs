@@ -0,0 +1,6 @@
+use typeshare_annotation::typeshare;
+
+#[typeshare(swift = 1)]
+pub struct Secret(Vec<u8>);
+
+fn main() {}
@@ -0,0 +1,8 @@
+use typeshare_annotation::typeshare;
+
+#[typeshare]
+pub enum Handler {
+    Boxed(Box<dyn Fn()>),
+}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use typeshare_annotation::typeshare;
+
+#[typeshare(kotlin = "String")]
+pub struct Secret(Vec<u8>);
+
+fn main() {}
@@ -0,0 +1,90 @@
+//! Case conversions for `#[serde(rename_all = "...")]`, matching serde's own rules.
+//!
+//! serde treats fields and variants differently: field names are expected to be `snake_case` and
+//! variant names `PascalCase`, so the same rule can produce different results for each.
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RenameRule {
+    LowerCase,
+    UpperCase,
+    PascalCase,
+    CamelCase,
+    SnakeCase,
+    ScreamingSnakeCase,
+    KebabCase,
+    ScreamingKebabCase,
+}
+
+impl RenameRule {
+    /// Parses a rule by the name serde accepts, returning `None` for unknown names.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "lowercase" => Some(RenameRule::LowerCase),
+            "UPPERCASE" => Some(RenameRule::UpperCase),
+            "PascalCase" => Some(RenameRule::PascalCase),
+            "camelCase" => Some(RenameRule::CamelCase),
+            "snake_case" => Some(RenameRule::SnakeCase),
+            "SCREAMING_SNAKE_CASE" => Some(RenameRule::ScreamingSnakeCase),
+            "kebab-case" => Some(RenameRule::KebabCase),
+            "SCREAMING-KEBAB-CASE" => Some(RenameRule::ScreamingKebabCase),
+            _ => None,
+        }
+    }
+
+    /// Applies the rule to a `PascalCase` enum variant name.
+    pub fn apply_to_variant(self, variant: &str) -> String {
+        match self {
+            RenameRule::PascalCase => variant.to_owned(),
+            RenameRule::LowerCase => variant.to_ascii_lowercase(),
+            RenameRule::UpperCase => variant.to_ascii_uppercase(),
+            RenameRule::CamelCase => lowercase_first(variant),
+            RenameRule::SnakeCase => {
+                let mut snake = String::new();
+                for (i, ch) in variant.char_indices() {
+                    if i > 0 && ch.is_uppercase() {
+                        snake.push('_');
+                    }
+                    snake.push(ch.to_ascii_lowercase());
+                }
+                snake
+            }
+            RenameRule::ScreamingSnakeCase => RenameRule::SnakeCase.apply_to_variant(variant).to_ascii_uppercase(),
+            RenameRule::KebabCase => RenameRule::SnakeCase.apply_to_variant(variant).replace('_', "-"),
+            RenameRule::ScreamingKebabCase => RenameRule::ScreamingSnakeCase.apply_to_variant(variant).replace('_', "-"),
+        }
+    }
+
+    /// Applies the rule to a `snake_case` field name.
+    pub fn apply_to_field(self, field: &str) -> String {
+        match self {
+            RenameRule::LowerCase | RenameRule::SnakeCase => field.to_owned(),
+            RenameRule::UpperCase | RenameRule::ScreamingSnakeCase => field.to_ascii_uppercase(),
+            RenameRule::PascalCase => {
+                let mut pascal = String::new();
+                let mut capitalize = true;
+                for ch in field.chars() {
+                    if ch == '_' {
+                        capitalize = true;
+                    } else if capitalize {
+                        pascal.push(ch.to_ascii_uppercase());
+                        capitalize = false;
+                    } else {
+                        pascal.push(ch);
+                    }
+                }
+                pascal
+            }
+            RenameRule::CamelCase => lowercase_first(&RenameRule::PascalCase.apply_to_field(field)),
+            RenameRule::KebabCase => field.replace('_', "-"),
+            RenameRule::ScreamingKebabCase => RenameRule::ScreamingSnakeCase.apply_to_field(field).replace('_', "-"),
+        }
+    }
+}
+
+fn lowercase_first(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+        None => String::new(),
+    }
+}
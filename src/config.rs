@@ -0,0 +1,74 @@
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::error::Error;
+
+/// Settings read from a TOML config file.
+///
+/// ```toml
+/// [types]
+/// "crate::Secret" = { ts = "string", swift = "SecureString", java = "char[]" }
+///
+/// [imports]
+/// swift = ["import SecureKit"]
+/// ```
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// Target-language types for Rust types, keyed by the Rust type path.
+    #[serde(default)]
+    pub types: BTreeMap<String, TypeMapping>,
+    /// Lines added to the header of each generated file, for the types above.
+    #[serde(default)]
+    pub imports: Imports,
+}
+
+/// The type to write for a Rust type in each language. Languages left out keep the Rust name.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TypeMapping {
+    pub ts: Option<String>,
+    pub swift: Option<String>,
+    pub java: Option<String>,
+}
+
+/// Header lines per language, written as they are.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Imports {
+    #[serde(default)]
+    pub ts: Vec<String>,
+    #[serde(default)]
+    pub swift: Vec<String>,
+    #[serde(default)]
+    pub java: Vec<String>,
+}
+
+impl Config {
+    pub fn parse(source: &str) -> Result<Config, toml::de::Error> {
+        toml::from_str(source)
+    }
+
+    pub fn from_file(path: &str) -> Result<Config, Box<dyn Error>> {
+        let source = std::fs::read_to_string(path)?;
+        Ok(Config::parse(&source)?)
+    }
+
+    /// The mapping for a type as written in the source. A leading `crate::` is ignored on both
+    /// sides, and a bare name matches the last segment of a configured path, since typeshare
+    /// can't resolve `use` declarations.
+    pub fn type_mapping(&self, path: &str) -> Option<&TypeMapping> {
+        let strip = |path: &str| path.trim_start_matches("crate::").to_string();
+        let path = strip(path);
+        self.types
+            .iter()
+            .find(|(key, _)| strip(key) == path)
+            .or_else(|| {
+                if path.contains("::") {
+                    None
+                } else {
+                    self.types.iter().find(|(key, _)| key.rsplit("::").next() == Some(path.as_str()))
+                }
+            })
+            .map(|(_, mapping)| mapping)
+    }
+}
@@ -1,19 +1,82 @@
 use inflector::Inflector;
 use std::io::Write;
 
-use crate::language::{Language, Params, RustStruct, ACRONYMS};
+use crate::config::TypeMapping;
+use crate::language::{BytesEncoding, Direction, JavaLargeIntegers, Language, Params, RustConstEnum, RustField, RustStruct, RustType, ACRONYMS};
 
+#[derive(Default)]
 pub struct Java {
     ids: Vec<String>,
+    /// Set once a type refers to `RustResult` for a `Result<T, E>`.
+    uses_result: bool,
+    /// Lengths of the tuples types refer to, each needing a `TupleN` record.
+    tuple_arities: Vec<usize>,
+    /// Set once a type refers to a `java.time` type, which needs Jackson's `JavaTimeModule`.
+    uses_java_time: bool,
 }
 
 impl Java {
     pub fn new() -> Self {
-        Java { ids: Vec::new() }
+        Java {
+            ids: Vec::new(),
+            uses_result: false,
+            tuple_arities: Vec::new(),
+            uses_java_time: false,
+        }
+    }
+
+    /// Records the helper types that `ty` refers to, for `end_file` to write.
+    fn note_helper_types(&mut self, ty: &RustType) {
+        ty.walk(&mut |ty| match ty {
+            RustType::Result(..) => self.uses_result = true,
+            RustType::Tuple(elems) if !elems.is_empty() => self.tuple_arities.push(elems.len()),
+            RustType::Other(path, _) if java_primitive(path).starts_with("java.time.") => self.uses_java_time = true,
+            _ => {}
+        });
+    }
+}
+
+fn java_type(s: &str, params: &Params) -> String {
+    java_type_of(&RustType::parse(s).resolve(params), params)
+}
+
+/// Generic arguments are boxed, and `Option` is left to the nullability of reference types.
+fn java_type_of(ty: &RustType, params: &Params) -> String {
+    if let RustType::Other(path, _) = ty {
+        if let Some(mapped) = params.config.type_mapping(path).and_then(|m| m.java.as_ref()) {
+            return mapped.clone();
+        }
+    }
+    let boxed = |ty: &RustType| java_boxed_type(&java_type_of(ty, params)).to_string();
+    match ty {
+        RustType::Vec(inner) | RustType::Array(inner, _) => format!("{}[]", java_type_of(inner, params)),
+        RustType::Set(inner) => format!("java.util.Set<{}>", boxed(inner)),
+        RustType::Map(key, value) => format!("java.util.Map<{}, {}>", boxed(key), boxed(value)),
+        RustType::Option(inner) => boxed(inner),
+        RustType::Result(ok, err) => format!("RustResult<{}, {}>", boxed(ok), boxed(err)),
+        // Jackson reads `null` into, and writes `null` from, `Void`.
+        RustType::Tuple(elems) if elems.is_empty() => "Void".to_string(),
+        RustType::Tuple(elems) => {
+            let elems: Vec<String> = elems.iter().map(boxed).collect();
+            format!("Tuple{}<{}>", elems.len(), elems.join(", "))
+        }
+        RustType::Other(path, args) if args.is_empty() => java_integer_type(path, params).to_string(),
+        RustType::Other(path, args) => {
+            let args: Vec<String> = args.iter().map(boxed).collect();
+            format!("{}<{}>", path, args.join(", "))
+        }
+    }
+}
+
+/// `long` can't hold every `u64`, so those can be typed as `BigInteger` instead.
+fn java_integer_type<'a>(s: &'a str, params: &Params) -> &'a str {
+    match s {
+        "u64" | "usize" if params.java_large_integers == JavaLargeIntegers::BigInteger => "java.math.BigInteger",
+        _ => java_primitive(s),
     }
 }
 
-fn java_type(s: &str) -> &str {
+fn java_primitive(s: &str) -> &str {
     match s {
         "str" | "String" => "String",
         "i8" => "byte",
@@ -21,20 +84,86 @@ fn java_type(s: &str) -> &str {
         "i32" => "int",
         "i64" => "long",
         "i128" => "java.math.BigInteger",
-        "u8" => "byte",
-        "u16" => "short",
-        "u32" => "int",
-        "u64" => "number",
+        // Java has no unsigned integers, so these widen to the next signed type.
+        "u8" => "short",
+        "u16" => "int",
+        "u32" => "long",
+        "u64" => "long",
+        "u128" => "java.math.BigInteger",
         "f32" => "float",
         "f64" => "double",
         "isize" => "long",
         "usize" => "long",
         "bool" => "boolean",
         "char" => "char",
+        "chrono::DateTime" => "java.time.OffsetDateTime",
+        "chrono::NaiveDateTime" => "java.time.LocalDateTime",
+        "chrono::NaiveDate" => "java.time.LocalDate",
+        "chrono::NaiveTime" => "java.time.LocalTime",
+        "uuid::Uuid" => "java.util.UUID",
+        "url::Url" => "java.net.URI",
+        "serde_json::Value" => "com.fasterxml.jackson.databind.JsonNode",
+        _ => s,
+    }
+}
+
+/// Java has no unsigned integers, so pick a type wide enough for every value of the `#[repr(...)]` type.
+fn java_repr_type(repr: &str) -> &'static str {
+    match repr {
+        "i8" | "i16" | "i32" | "u8" | "u16" => "int",
+        "i128" | "u128" => "java.math.BigInteger",
+        _ => "long",
+    }
+}
+
+/// `value` as a literal of the Java type for `repr`.
+fn java_repr_value(value: i128, repr: &str) -> String {
+    match java_repr_type(repr) {
+        "long" => format!("{}L", value),
+        "java.math.BigInteger" => format!("new java.math.BigInteger(\"{}\")", value),
+        _ => value.to_string(),
+    }
+}
+
+/// Boxed equivalent of a primitive type, for use as a generic argument.
+fn java_boxed_type(s: &str) -> &str {
+    match s {
+        "byte" => "Byte",
+        "short" => "Short",
+        "int" => "Integer",
+        "long" => "Long",
+        "float" => "Float",
+        "double" => "Double",
+        "boolean" => "Boolean",
+        "char" => "Character",
         _ => s,
     }
 }
 
+/// Optional fields are boxed, so they can hold `null`.
+fn java_field_type(rf: &RustField, params: &Params) -> String {
+    let ty = if let Some(ty) = &rf.type_override.java {
+        ty.clone()
+    } else if let Some(bytes) = rf.bytes {
+        java_bytes_type(bytes).to_string()
+    } else {
+        java_type_of(&rf.rust_type(params), params)
+    };
+    if rf.is_optional {
+        java_boxed_type(&ty).to_string()
+    } else {
+        ty
+    }
+}
+
+/// Jackson writes `byte[]` as base64, so bytes sent as an array of numbers use `int[]` to hold 0..=255.
+fn java_bytes_type(bytes: BytesEncoding) -> &'static str {
+    match bytes {
+        BytesEncoding::Array => "int[]",
+        BytesEncoding::Base64 => "byte[]",
+    }
+}
+
 impl Language for Java {
     fn begin_file(&mut self, w: &mut dyn Write, _params: &Params) -> std::io::Result<()> {
         writeln!(w, "/**")?;
@@ -45,21 +174,44 @@ impl Language for Java {
         Ok(())
     }
 
+    fn mapped_type<'a>(&self, mapping: &'a TypeMapping) -> Option<&'a str> {
+        mapping.java.as_deref()
+    }
+
+    fn lossy_type(&self, ty: &str, params: &Params) -> Option<String> {
+        if matches!(ty, "u64" | "usize") && params.java_large_integers == JavaLargeIntegers::Long {
+            Some(format!("`{}` is typed as `long` in Java, which can't hold values of 2^63 and above", ty))
+        } else {
+            None
+        }
+    }
+
     fn write_struct(&mut self, w: &mut dyn Write, _params: &Params, rs: &RustStruct) -> std::io::Result<()> {
         writeln!(w, "package {};", _params.java_package)?;
         writeln!(w)?;
         writeln!(w, "import com.fasterxml.jackson.annotation.*;")?;
+        write_imports(w, _params)?;
         writeln!(w)?;
 
         write_comments(w, 0, &rs.comments)?;
+        if rs.deny_unknown_fields {
+            writeln!(w, "@JsonIgnoreProperties(ignoreUnknown = false)")?;
+        }
         writeln!(w, "public class {} {{", rs.id.original)?;
         self.ids.push(rs.id.original.clone());
+        for rf in rs.fields.iter() {
+            self.note_helper_types(&rf.rust_type(_params));
+        }
 
         for rf in rs.fields.iter() {
-            if rf.is_vec {
-                writeln!(w, "\tprivate {} {}[];", java_type(&rf.ty), rf.id.renamed)?;
+            let initialiser = match java_default_value(rf, _params) {
+                Some(value) if rf.has_default || rf.is_catch_all() => format!(" = {}", value),
+                _ => "".to_string(),
+            };
+            if rf.is_vec && rf.type_override.java.is_none() {
+                writeln!(w, "\tprivate {} {}[]{};", java_type(&rf.ty, _params), rf.id.identifier(), initialiser)?;
             } else {
-                writeln!(w, "\tprivate {} {};", java_type(&rf.ty), rf.id.renamed)?;
+                writeln!(w, "\tprivate {} {}{};", java_field_type(rf, _params), rf.id.identifier(), initialiser)?;
             }
         }
         writeln!(w)?;
@@ -68,7 +220,89 @@ impl Language for Java {
         Ok(())
     }
 
+    fn write_transparent_struct(&mut self, w: &mut dyn Write, _params: &Params, rs: &RustStruct) -> std::io::Result<()> {
+        writeln!(w, "package {};", _params.java_package)?;
+        writeln!(w)?;
+        writeln!(w, "import com.fasterxml.jackson.annotation.*;")?;
+        write_imports(w, _params)?;
+        writeln!(w)?;
+
+        // Jackson reads and writes the wrapper as the bare inner value.
+        let rf = &rs.fields[0];
+        write_comments(w, 0, &rs.comments)?;
+        writeln!(w, "public class {} {{", rs.id.original)?;
+        self.ids.push(rs.id.original.clone());
+        self.note_helper_types(&rf.rust_type(_params));
+        writeln!(w, "\tprivate {} value;", java_field_type(rf, _params))?;
+        writeln!(w)?;
+        writeln!(w, "\t@JsonCreator")?;
+        writeln!(w, "\tpublic {}({} value) {{ this.value = value; }}", rs.id.original, java_field_type(rf, _params))?;
+        writeln!(w)?;
+        writeln!(w, "\t@JsonValue")?;
+        writeln!(w, "\tpublic {} getValue() {{ return value; }}", java_field_type(rf, _params))?;
+        writeln!(w, "}}\n")?;
+        Ok(())
+    }
+
+    fn write_const_enum(&mut self, w: &mut dyn Write, _params: &Params, e: &RustConstEnum) -> std::io::Result<()> {
+        writeln!(w, "package {};", _params.java_package)?;
+        writeln!(w)?;
+        writeln!(w, "import com.fasterxml.jackson.annotation.*;")?;
+        write_imports(w, _params)?;
+        writeln!(w)?;
+
+        write_comments(w, 0, &e.comments)?;
+        writeln!(w, "public enum {} {{", e.id.original)?;
+        for c in e.cases.iter() {
+            if !c.comments.is_empty() {
+                write_comments(w, 1, &c.comments)?;
+            }
+            if c.is_other {
+                writeln!(w, "\t@JsonEnumDefaultValue")?;
+            }
+            match (c.repr_value, &e.repr) {
+                (Some(value), Some(repr)) => writeln!(w, "\t{}({}),", c.id.original, java_repr_value(value, repr))?,
+                _ => {
+                    writeln!(w, "\t@JsonProperty(\"{}\")", c.id.renamed)?;
+                    writeln!(w, "\t{},", c.id.original)?;
+                }
+            }
+        }
+        // `#[non_exhaustive]` without a `#[serde(other)]` case still needs somewhere for new values
+        // to go. A `serde_repr` enum has no spare value to give it, so only `other` works there.
+        if e.accepts_unknown && e.repr.is_none() && !e.cases.iter().any(|c| c.is_other) {
+            // Named after the cases, such as `Unknown_` next to an `Unknown` case.
+            let mut name = "Unknown".to_string();
+            while e.cases.iter().any(|c| c.id.original == name) {
+                name.push('_');
+            }
+            write_comments(w, 1, &vec!["A case added after this type was generated.".to_string()])?;
+            writeln!(w, "\t@JsonEnumDefaultValue")?;
+            writeln!(w, "\t{},", name)?;
+        }
+
+        if let Some(repr) = &e.repr {
+            // Jackson writes and reads `serde_repr` enums by the `@JsonValue` number.
+            let value_type = java_repr_type(repr);
+            writeln!(w, "\t;\n")?;
+            writeln!(w, "\tprivate final {} value;\n", value_type)?;
+            writeln!(w, "\t{}({} value) {{ this.value = value; }}\n", e.id.original, value_type)?;
+            writeln!(w, "\t@JsonValue")?;
+            writeln!(w, "\tpublic {} getValue() {{ return value; }}", value_type)?;
+        }
+        writeln!(w, "}}\n")?;
+        Ok(())
+    }
+
     fn end_file(&mut self, w: &mut dyn Write, _params: &Params) -> std::io::Result<()> {
+        if self.uses_result {
+            write_result(w, _params)?;
+        }
+        self.tuple_arities.sort_unstable();
+        self.tuple_arities.dedup();
+        for arity in self.tuple_arities.iter() {
+            write_tuple(w, _params, *arity)?;
+        }
         writeln!(w, "package {};", _params.java_package)?;
         writeln!(w)?;
         writeln!(w, "import java.io.IOException;")?;
@@ -81,12 +315,127 @@ impl Language for Java {
         writeln!(w, "public class Converter {{")?;
         writeln!(w)?;
         write_string_converter(&self.ids, 2, w)?;
-        write_reader_writer_converter(&self.ids, 2, w)?;
+        write_reader_writer_converter(&self.ids, self.uses_java_time, 2, w)?;
         writeln!(w, "}}")?;
         Ok(())
     }
 }
 
+/// Initialiser matching the value serde fills in for a missing `#[serde(default)]` field, if
+/// Java has one. Types without a known zero value or a no-arg constructor are left `null`.
+fn java_default_value(rf: &RustField, params: &Params) -> Option<String> {
+    if rf.is_optional {
+        return Some("null".to_string());
+    }
+    if let Some(ty) = &rf.type_override.java {
+        return java_default_for(ty, params);
+    }
+    if let Some(bytes) = rf.bytes {
+        return Some(format!("new {}[0]", java_bytes_type(bytes).trim_end_matches("[]")));
+    }
+    if rf.is_vec {
+        return Some(format!("new {}[0]", java_type(&rf.ty, params)));
+    }
+    if rf.is_hash_map {
+        return Some("new java.util.HashMap<>()".to_string());
+    }
+    match rf.rust_type(params) {
+        RustType::Set(_) => Some("new java.util.HashSet<>()".to_string()),
+        RustType::Array(inner, len) => Some(format!("new {}[{}]", java_type_of(&inner, params), len)),
+        _ => java_default_for(&java_type(&rf.ty, params), params),
+    }
+}
+
+/// Initialiser for a field of Java type `ty` with its zero value: primitives, strings, arrays, and
+/// the classes written for structs, which have a no-arg constructor.
+fn java_default_for(ty: &str, params: &Params) -> Option<String> {
+    let value = match ty {
+        "String" => r#""""#.to_string(),
+        "byte" | "short" | "int" | "long" | "float" | "double" => "0".to_string(),
+        "boolean" => "false".to_string(),
+        "char" => r"'\0'".to_string(),
+        "java.math.BigInteger" => "java.math.BigInteger.ZERO".to_string(),
+        ty if ty.ends_with("[]") => format!("new {}[0]", ty.trim_end_matches("[]")),
+        ty if params.object_types.contains_key(ty) => format!("new {}()", ty),
+        _ => return None,
+    };
+    Some(value)
+}
+
+/// Writes the imports from the config file, which the types it maps may need.
+fn write_imports(w: &mut dyn Write, params: &Params) -> std::io::Result<()> {
+    for line in params.config.imports.java.iter() {
+        writeln!(w, "{}", line)?;
+    }
+    Ok(())
+}
+
+/// Writes the class for `Result<T, E>`, which holds one of its values under an `Ok` or `Err` key.
+fn write_result(w: &mut dyn Write, params: &Params) -> std::io::Result<()> {
+    writeln!(w, "package {};", params.java_package)?;
+    writeln!(w)?;
+    writeln!(w, "import com.fasterxml.jackson.annotation.*;")?;
+    writeln!(w)?;
+    write_comments(
+        w,
+        0,
+        &vec!["The outcome of a fallible operation, written as {\"Ok\": value} or {\"Err\": error} like Rust's Result<T, E>.".to_string()],
+    )?;
+    writeln!(w, "public class RustResult<T, E> {{")?;
+    writeln!(w, "\tprivate T ok;")?;
+    writeln!(w, "\tprivate E err;")?;
+    writeln!(w, "\tprivate boolean isErr;")?;
+    writeln!(w)?;
+    // The value may itself be null, as with `Ok(())`, so the key is written whichever it is.
+    writeln!(w, "\t@JsonAnyGetter")?;
+    writeln!(
+        w,
+        "\tpublic java.util.Map<String, Object> toJson() {{ return java.util.Collections.singletonMap(isErr ? \"Err\" : \"Ok\", isErr ? err : ok); }}"
+    )?;
+    writeln!(w)?;
+    writeln!(w, "\t@JsonIgnore")?;
+    writeln!(w, "\tpublic T getOk() {{ return ok; }}")?;
+    writeln!(w, "\t@JsonProperty(\"Ok\")")?;
+    writeln!(w, "\tpublic void setOk(T value) {{ this.ok = value; this.err = null; this.isErr = false; }}")?;
+    writeln!(w)?;
+    writeln!(w, "\t@JsonIgnore")?;
+    writeln!(w, "\tpublic E getErr() {{ return err; }}")?;
+    writeln!(w, "\t@JsonProperty(\"Err\")")?;
+    writeln!(w, "\tpublic void setErr(E value) {{ this.err = value; this.ok = null; this.isErr = true; }}")?;
+    writeln!(w)?;
+    writeln!(w, "\t@JsonIgnore")?;
+    writeln!(w, "\tpublic boolean isErr() {{ return isErr; }}")?;
+    writeln!(w, "}}\n")?;
+    Ok(())
+}
+
+/// Writes the class for tuples of `arity` elements, which Jackson reads and writes as an array.
+fn write_tuple(w: &mut dyn Write, params: &Params, arity: usize) -> std::io::Result<()> {
+    let generics: Vec<String> = (0..arity).map(|i| format!("T{}", i)).collect();
+    let names: Vec<String> = (0..arity).map(|i| format!("\"_{}\"", i)).collect();
+
+    writeln!(w, "package {};", params.java_package)?;
+    writeln!(w)?;
+    writeln!(w, "import com.fasterxml.jackson.annotation.*;")?;
+    writeln!(w)?;
+    write_comments(w, 0, &vec![format!("A Rust tuple of {} elements, written as a JSON array.", arity)])?;
+    writeln!(w, "@JsonFormat(shape = JsonFormat.Shape.ARRAY)")?;
+    writeln!(w, "@JsonPropertyOrder({{{}}})", names.join(", "))?;
+    writeln!(w, "public class Tuple{}<{}> {{", arity, generics.join(", "))?;
+    for i in 0..arity {
+        writeln!(w, "\tprivate T{0} _{0};", i)?;
+    }
+    for i in 0..arity {
+        writeln!(w)?;
+        writeln!(w, "\t@JsonProperty(\"_{}\")", i)?;
+        writeln!(w, "\tpublic T{0} get_{0}() {{ return _{0}; }}", i)?;
+        writeln!(w, "\t@JsonProperty(\"_{}\")", i)?;
+        writeln!(w, "\tpublic void set_{0}(T{0} value) {{ this._{0} = value; }}", i)?;
+    }
+    writeln!(w, "}}\n")?;
+    Ok(())
+}
+
 fn write_string_converter(ids: &Vec<String>, indent: usize, w: &mut dyn Write) -> std::io::Result<()> {
     for id in ids {
         writeln!(w, "{}public static {1} {1}FromJsonString(String json) throws IOException {{", "\t".repeat(indent - 1), id)?;
@@ -106,13 +455,23 @@ fn write_string_converter(ids: &Vec<String>, indent: usize, w: &mut dyn Write) -
     Ok(())
 }
 
-fn write_reader_writer_converter(ids: &Vec<String>, indent: usize, w: &mut dyn Write) -> std::io::Result<()> {
+fn write_reader_writer_converter(ids: &Vec<String>, uses_java_time: bool, indent: usize, w: &mut dyn Write) -> std::io::Result<()> {
     for id in ids {
         writeln!(w, "{}private static ObjectReader {}Reader;", "\t".repeat(indent - 1), id)?;
         writeln!(w, "{}private static ObjectWriter {}Writer;", "\t".repeat(indent - 1), id)?;
         writeln!(w)?;
         writeln!(w, "{}private static void instantiate{}Mapper() {{", "\t".repeat(indent - 1), id)?;
         writeln!(w, "{}ObjectMapper mapper = new ObjectMapper();", "\t".repeat(indent))?;
+        writeln!(
+            w,
+            "{}mapper.enable(DeserializationFeature.READ_UNKNOWN_ENUM_VALUES_USING_DEFAULT_VALUE);",
+            "\t".repeat(indent)
+        )?;
+        if uses_java_time {
+            // Dates are ISO 8601 strings, as chrono writes them.
+            writeln!(w, "{}mapper.findAndRegisterModules();", "\t".repeat(indent))?;
+            writeln!(w, "{}mapper.disable(SerializationFeature.WRITE_DATES_AS_TIMESTAMPS);", "\t".repeat(indent))?;
+        }
         writeln!(w, "{}{1}Reader = mapper.readerFor({1}.class);", "\t".repeat(indent), id)?;
         writeln!(w, "{}{1}Writer = mapper.writerFor({1}.class);", "\t".repeat(indent), id)?;
         writeln!(w, "{}}}", "\t".repeat(indent - 1))?;
@@ -133,16 +492,66 @@ fn write_reader_writer_converter(ids: &Vec<String>, indent: usize, w: &mut dyn W
 
 fn write_getter_setter(w: &mut dyn Write, _generator_params: &Params, rs: &RustStruct) -> std::io::Result<()> {
     for rf in rs.fields.iter() {
-        write_comments(w, 1, &rf.comments)?;
-        writeln!(w, "\t@JsonProperty(\"{0}\")", rf.id.renamed)?;
-        let mut method_name = rf.id.renamed.to_pascal_case();
+        write_comments(w, 1, &rf.comments_with_len())?;
+        if let (true, Some((_, value))) = (rf.is_catch_all(), rf.map_types()) {
+            // Jackson routes every unknown property through the any-setter.
+            let method_name = rf.id.identifier().to_pascal_case();
+            writeln!(w, "\t@JsonAnyGetter")?;
+            writeln!(
+                w,
+                "\tpublic {} get{}() {{ return {}; }}",
+                java_field_type(rf, _generator_params),
+                method_name,
+                rf.id.identifier()
+            )?;
+            writeln!(w, "\t@JsonAnySetter")?;
+            writeln!(
+                w,
+                "\tpublic void set{}(String key, {} value) {{ this.{}.put(key, value); }}",
+                method_name,
+                java_boxed_type(&java_type(value, _generator_params)),
+                rf.id.identifier()
+            )?;
+            writeln!(w)?;
+            continue;
+        }
+
+        // Flattened fields are read from and written to the parent object. Otherwise the getter
+        // writes the name Rust deserializes, and the setter reads the name Rust serializes.
+        let (getter_annotation, setter_annotation) = if rf.is_flattened {
+            ("@JsonUnwrapped".to_string(), "@JsonUnwrapped".to_string())
+        } else {
+            (format!("@JsonProperty(\"{}\")", rf.id.renamed_deserialize), format!("@JsonProperty(\"{}\")", rf.id.renamed))
+        };
+        writeln!(w, "\t{}", getter_annotation)?;
+        if rf.is_optional && !rf.is_nullable(Direction::Output) {
+            // Matches `skip_serializing_if = "Option::is_none"`, which never writes `null`.
+            writeln!(w, "\t@JsonInclude(JsonInclude.Include.NON_NULL)")?;
+        }
+        let mut method_name = rf.id.identifier().to_pascal_case();
         if ACRONYMS.contains(&rf.id.renamed.as_str()) {
             method_name = rf.id.renamed.to_uppercase();
         }
 
-        writeln!(w, "\tpublic {} get{}() {{ return {}; }}", java_type(&rf.ty), method_name, rf.id.renamed)?;
-        writeln!(w, "\t@JsonProperty(\"{0}\")", rf.id.renamed)?;
-        writeln!(w, "\tpublic void set{}({} value) {{ this.{} = value; }}", method_name, java_type(&rf.ty), rf.id.renamed)?;
+        writeln!(
+            w,
+            "\tpublic {} get{}() {{ return {}; }}",
+            java_field_type(rf, _generator_params),
+            method_name,
+            rf.id.identifier()
+        )?;
+        writeln!(w, "\t{}", setter_annotation)?;
+        if !rf.id.aliases.is_empty() {
+            let aliases: Vec<String> = rf.id.aliases.iter().map(|a| format!("\"{}\"", a)).collect();
+            writeln!(w, "\t@JsonAlias({{{}}})", aliases.join(", "))?;
+        }
+        writeln!(
+            w,
+            "\tpublic void set{}({} value) {{ this.{} = value; }}",
+            method_name,
+            java_field_type(rf, _generator_params),
+            rf.id.identifier()
+        )?;
         writeln!(w)?;
     }
 
@@ -157,7 +566,7 @@ fn write_comment(w: &mut dyn Write, indent: usize, comment: &str) -> std::io::Re
 fn write_comments(w: &mut dyn Write, indent: usize, comments: &Vec<String>) -> std::io::Result<()> {
     writeln!(w, "{}/**", "\t".repeat(indent))?;
     for c in comments {
-        write_comment(w, indent, &c)?;
+        write_comment(w, indent, c)?;
     }
     writeln!(w, "{} */", "\t".repeat(indent))?;
     Ok(())
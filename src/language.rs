@@ -1,8 +1,13 @@
 use proc_macro2::{Ident, Span};
-use std::{error::Error, fs, io::Write};
-use syn;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    error::Error,
+    fs,
+    io::Write,
+};
 
-use inflector::Inflector;
+use crate::case::RenameRule;
+use crate::config::{Config, TypeMapping};
 
 const COMMENT_PREFIX: &str = "= \" ";
 const COMMENT_SUFFIX: &str = "\"";
@@ -10,13 +15,7 @@ const COMMENT_SUFFIX: &str = "\"";
 const OPTION_PREFIX: &str = "Option < ";
 const OPTION_SUFFIX: &str = " >";
 
-const VEC_PREFIX: &str = "Vec < ";
-const VEC_SUFFIX: &str = " >";
-
-const HASH_MAP_PREFIX: &str = "HashMap < ";
-const HASH_MAP_SUFFIX: &str = " >";
-
-pub const ACRONYMS: &'static [&'static str] = &[
+pub const ACRONYMS: &[&str] = &[
     "aaa", "aabb", "aac", "aal", "aalc", "aarp", "abac", "abcl", "abi", "abm", "abr", "ac", "acd", "ack", "acl", "acm", "acme", "acp", "acpi", "acr", "adb", "adc", "adccp", "ado",
     "adsl", "adt", "ae", "aes", "af", "afp", "agp", "ai", "aix", "alac", "algol", "alsa", "alu", "amd", "amoled", "amqp", "amr", "ann", "ansi", "aop", "apci", "api", "apic",
     "apipa", "apl", "apr", "arin", "aros", "arp", "arpa", "arpanet", "ascii", "aset", "asg", "asic", "asimo", "aslr", "asm", "asmp", "asp", "asr", "assp", "ast", "ata", "atag",
@@ -70,7 +69,43 @@ pub const ACRONYMS: &'static [&'static str] = &[
 #[derive(Clone)]
 pub struct Id {
     pub original: String,
+    /// Name used when serializing.
     pub renamed: String,
+    /// Name used when deserializing, which differs from `renamed` with `rename(deserialize = "...")`.
+    pub renamed_deserialize: String,
+    /// Extra names accepted when deserializing, from `#[serde(alias = "...")]`.
+    pub aliases: Vec<String>,
+}
+
+impl Id {
+    /// Name of this identifier in JSON travelling in `direction`.
+    pub fn renamed_for(&self, direction: Direction) -> &str {
+        match direction {
+            Direction::Input => &self.renamed_deserialize,
+            Direction::Both | Direction::Output => &self.renamed,
+        }
+    }
+
+    /// `renamed` as an identifier in languages that can't use any string as a name, such as
+    /// `userId` for `user-id` and `maxSize` for `MAX-SIZE`. Backends map it back to `renamed` on the wire.
+    pub fn identifier(&self) -> String {
+        if !self.renamed.contains(|c: char| !c.is_alphanumeric() && c != '_') && self.renamed.starts_with(|c: char| c.is_alphabetic() || c == '_') {
+            return self.renamed.clone();
+        }
+        let mut identifier = String::new();
+        for word in self.renamed.split(|c: char| !c.is_alphanumeric() && c != '_').filter(|word| !word.is_empty()) {
+            let word = if word.chars().any(char::is_lowercase) { word.to_string() } else { word.to_lowercase() };
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) if !identifier.is_empty() => identifier.extend(first.to_uppercase().chain(chars)),
+                _ => identifier.push_str(&word),
+            }
+        }
+        if !identifier.starts_with(|c: char| c.is_alphabetic() || c == '_') {
+            identifier.insert(0, '_');
+        }
+        identifier
+    }
 }
 
 impl std::fmt::Display for Id {
@@ -83,21 +118,295 @@ impl std::fmt::Display for Id {
     }
 }
 
+/// Direction in which a type or field travels through `serde`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Direction {
+    /// Both serialized and deserialized.
+    Both,
+    /// Only deserialized by Rust (`#[serde(skip_serializing)]`), i.e. what clients send.
+    Input,
+    /// Only serialized by Rust (`#[serde(skip_deserializing)]`), i.e. what clients receive.
+    Output,
+}
+
+impl Direction {
+    /// Suffix appended to the type name when input and output types are emitted separately.
+    pub fn type_suffix(self) -> &'static str {
+        match self {
+            Direction::Both => "",
+            Direction::Input => "Request",
+            Direction::Output => "Response",
+        }
+    }
+
+    /// Whether a field travelling in `self` direction is present in a type travelling in `direction`.
+    pub fn includes(self, direction: Direction) -> bool {
+        self == Direction::Both || direction == Direction::Both || self == direction
+    }
+}
+
 /// Rust struct.
+#[derive(Clone)]
 pub struct RustStruct {
     pub id: Id,
     pub fields: Vec<RustField>,
     pub comments: Vec<String>,
+    /// `Both` unless this is one half of a struct split into separate input and output types.
+    pub direction: Direction,
+    /// Container-level `#[serde(default)]`: any field may be missing when deserializing.
+    pub has_default: bool,
+    /// `#[serde(transparent)]`: serialized as its only field.
+    pub is_transparent: bool,
+    /// `#[serde(deny_unknown_fields)]`: deserializing fails on keys that aren't fields.
+    pub deny_unknown_fields: bool,
+}
+
+impl RustStruct {
+    /// Returns true if the input and output shapes of this struct differ, because a field is
+    /// one-way, may be left out of the input, or is renamed differently in each direction.
+    pub fn differs_by_direction(&self) -> bool {
+        self.fields
+            .iter()
+            .any(|f| f.direction != Direction::Both || f.has_default || f.id.renamed_deserialize != f.id.renamed)
+    }
+
+    /// Keys a strict deserializer of this struct accepts: the field names used in its direction,
+    /// and their aliases when Rust reads it. `None` if a flattened field means any key might belong to it.
+    pub fn known_keys(&self) -> Option<Vec<&str>> {
+        if self.fields.iter().any(|f| f.is_flattened) {
+            return None;
+        }
+
+        let mut keys = Vec::new();
+        for f in self.fields.iter() {
+            let serialized = Some(f.id.renamed.as_str()).filter(|_| self.direction != Direction::Input);
+            let deserialized = Some(f.id.renamed_deserialize.as_str()).filter(|_| self.direction != Direction::Output);
+            let aliases = f.id.aliases.iter().map(String::as_str).filter(|_| self.direction != Direction::Output);
+            for key in serialized.into_iter().chain(deserialized).chain(aliases) {
+                if !keys.contains(&key) {
+                    keys.push(key);
+                }
+            }
+        }
+        Some(keys)
+    }
+
+    /// Builds the input or output half of this struct, keeping only the fields that travel in that direction.
+    pub fn for_direction(&self, direction: Direction) -> RustStruct {
+        let suffix = direction.type_suffix();
+        RustStruct {
+            id: Id {
+                original: format!("{}{}", self.id.original, suffix),
+                renamed: format!("{}{}", self.id.renamed, suffix),
+                renamed_deserialize: format!("{}{}", self.id.renamed_deserialize, suffix),
+                aliases: self.id.aliases.clone(),
+            },
+            fields: self.fields.iter().filter(|f| f.direction.includes(direction)).cloned().collect(),
+            comments: self.comments.clone(),
+            direction,
+            has_default: self.has_default,
+            is_transparent: self.is_transparent,
+            deny_unknown_fields: self.deny_unknown_fields,
+        }
+    }
 }
 
 /// Rust field defintion.
+#[derive(Clone)]
 pub struct RustField {
     pub id: Id,
     pub ty: String,
+    /// Declared as `Option<T>`.
     pub is_optional: bool,
+    /// Declared as `Option<Option<T>>`, distinguishing a missing key from an explicit `null`.
+    pub is_double_option: bool,
     pub is_vec: bool,
     pub is_hash_map: bool,
     pub comments: Vec<String>,
+    /// `Input` for `#[serde(skip_serializing)]` fields, `Output` for `#[serde(skip_deserializing)]` fields.
+    pub direction: Direction,
+    /// `#[serde(default)]` on the field or its container: the field may be missing when deserializing.
+    pub has_default: bool,
+    /// `#[serde(flatten)]`: the members of the field's type are inlined into the parent.
+    pub is_flattened: bool,
+    /// `#[serde(skip_serializing_if = "...")]`: the key is left out of serialized output when the predicate holds.
+    pub skip_serializing_if: bool,
+    /// Set for byte buffers such as `Vec<u8>` and `bytes::Bytes`, with how they're written to JSON.
+    pub bytes: Option<BytesEncoding>,
+    /// Types from `#[typeshare(ts = "...", swift = "...", java = "...")]`, written instead of the
+    /// field's type below `Option`.
+    pub type_override: TypeMapping,
+    /// The length `N` of a `[T; N]` array, which TypeScript writes as a tuple.
+    pub fixed_len: Option<String>,
+}
+
+/// How a byte buffer is written to JSON.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BytesEncoding {
+    /// An array of numbers, as serde_json writes `Vec<u8>`, `serde_bytes` and `bytes::Bytes`.
+    Array,
+    /// A base64 string, as written by base64 `with` modules and `serde_with`'s `Base64`.
+    Base64,
+}
+
+impl RustField {
+    /// `comments`, followed by the length of a fixed-size array for languages that type it as any array.
+    pub fn comments_with_len(&self) -> Vec<String> {
+        let mut comments = self.comments.clone();
+        comments.extend(self.fixed_len.iter().map(|len| format!("Holds exactly {} elements.", len)));
+        comments
+    }
+
+    /// Whether the key may be missing from JSON travelling in `direction`.
+    /// Defaulted fields are always written, so only input types treat them as omittable.
+    pub fn is_omittable(&self, direction: Direction) -> bool {
+        let input = self.is_optional || self.direction == Direction::Output;
+        let output = self.skip_serializing_if || self.direction == Direction::Input;
+        match direction {
+            Direction::Input => input || self.has_default,
+            Direction::Output => output,
+            Direction::Both => input || output,
+        }
+    }
+
+    /// Whether the value may be `null` in JSON travelling in `direction`. An `Option` skipped when
+    /// `None` is never written as `null`, unless it wraps another `Option`.
+    pub fn is_nullable(&self, direction: Direction) -> bool {
+        match direction {
+            Direction::Output => self.is_optional && (!self.skip_serializing_if || self.is_double_option),
+            Direction::Input | Direction::Both => self.is_optional,
+        }
+    }
+
+    /// Key and value types of a map field.
+    pub fn map_types(&self) -> Option<(&str, &str)> {
+        if !self.is_hash_map {
+            return None;
+        }
+        let mut types = self.ty.splitn(2, ',').map(|v| v.trim());
+        Some((types.next()?, types.next()?))
+    }
+
+    /// A flattened map collects every key that doesn't belong to another field, e.g.
+    /// `#[serde(flatten)] extra: HashMap<String, Value>`.
+    pub fn is_catch_all(&self) -> bool {
+        self.is_flattened && self.is_hash_map
+    }
+
+    /// The field's type below any `Option`, including the `Vec` or `HashMap` it was declared as,
+    /// with std and crate types resolved to what they're written like.
+    pub fn rust_type(&self, params: &Params) -> RustType {
+        let ty = if let Some((key, value)) = self.map_types() {
+            RustType::Map(Box::new(RustType::parse(key)), Box::new(RustType::parse(value)))
+        } else if self.is_vec {
+            RustType::Vec(Box::new(RustType::parse(&self.ty)))
+        } else {
+            RustType::parse(&self.ty)
+        };
+        ty.resolve(params)
+    }
+}
+
+/// A type parsed from its tokens, so backends can map generic arguments recursively.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RustType {
+    /// `Vec<T>`, and the other sequences serde writes as a JSON array: `VecDeque`, `LinkedList` and `BinaryHeap`.
+    Vec(Box<RustType>),
+    /// `[T; N]`, written as a JSON array of exactly `N` elements. `N` may name a constant.
+    Array(Box<RustType>, String),
+    /// `HashSet<T>`, `BTreeSet<T>` or `IndexSet<T>`.
+    Set(Box<RustType>),
+    /// `HashMap<K, V>`, `BTreeMap<K, V>` or `IndexMap<K, V>`.
+    Map(Box<RustType>, Box<RustType>),
+    /// `Option<T>`.
+    Option(Box<RustType>),
+    /// `Result<T, E>`, serialized as `{"Ok": T}` or `{"Err": E}`.
+    Result(Box<RustType>, Box<RustType>),
+    /// `(A, B, ...)`, written as a JSON array of its elements. The unit type `()` is written as `null`.
+    Tuple(Vec<RustType>),
+    /// Any other type: its path, and its generic arguments if it has any.
+    Other(String, Vec<RustType>),
+}
+
+impl RustType {
+    /// Parses the tokens of a type, such as `Result < Vault , ApiError >`.
+    pub fn parse(ty: &str) -> RustType {
+        let ty = ty.trim();
+        if ty.starts_with('(') && ty.ends_with(')') {
+            return RustType::Tuple(split_type_args(&ty[1..ty.len() - 1]).into_iter().map(RustType::parse).collect());
+        }
+        // The outermost length comes last, after any nested arrays.
+        if let (true, Some(semicolon)) = (ty.starts_with('[') && ty.ends_with(']'), ty.rfind(';')) {
+            let len = ty[semicolon + 1..ty.len() - 1].trim().to_string();
+            return RustType::Array(Box::new(RustType::parse(&ty[1..semicolon])), len);
+        }
+        let (path, args) = match ty.find('<') {
+            Some(start) if ty.ends_with('>') && is_type_path(&ty[..start]) => (&ty[..start], split_type_args(&ty[start + 1..ty.len() - 1])),
+            _ => (ty, Vec::new()),
+        };
+        let path = path.trim().replace(" :: ", "::");
+        let mut args: Vec<RustType> = args.into_iter().map(RustType::parse).collect();
+
+        let name = path.rsplit("::").next().unwrap_or_default();
+        match (name, args.len()) {
+            ("Vec", 1) | ("VecDeque", 1) | ("LinkedList", 1) | ("BinaryHeap", 1) => RustType::Vec(Box::new(args.remove(0))),
+            ("HashSet", 1) | ("BTreeSet", 1) | ("IndexSet", 1) => RustType::Set(Box::new(args.remove(0))),
+            ("HashMap", 2) | ("BTreeMap", 2) | ("IndexMap", 2) => {
+                let key = args.remove(0);
+                RustType::Map(Box::new(key), Box::new(args.remove(0)))
+            }
+            ("Option", 1) => RustType::Option(Box::new(args.remove(0))),
+            ("Result", 2) => {
+                let ok = args.remove(0);
+                RustType::Result(Box::new(ok), Box::new(args.remove(0)))
+            }
+            _ => RustType::Other(path, args),
+        }
+    }
+
+    /// Replaces the std and crate types serde doesn't write as their own name with the type they're
+    /// written like, such as `String` for `PathBuf`. See `Params::wire_type`.
+    pub fn resolve(&self, params: &Params) -> RustType {
+        let resolve = |ty: &RustType| Box::new(ty.resolve(params));
+        match self {
+            RustType::Vec(inner) => RustType::Vec(resolve(inner)),
+            RustType::Array(inner, len) => RustType::Array(resolve(inner), len.clone()),
+            RustType::Set(inner) => RustType::Set(resolve(inner)),
+            RustType::Map(key, value) => RustType::Map(resolve(key), resolve(value)),
+            RustType::Option(inner) => RustType::Option(resolve(inner)),
+            RustType::Result(ok, err) => RustType::Result(resolve(ok), resolve(err)),
+            RustType::Tuple(elems) => RustType::Tuple(elems.iter().map(|elem| elem.resolve(params)).collect()),
+            RustType::Other(path, args) => match params.wire_type(path, args) {
+                // Canonical paths such as `uuid::Uuid` map to themselves.
+                Some(wire_type @ RustType::Other(..)) => wire_type,
+                Some(wire_type) => wire_type.resolve(params),
+                None => RustType::Other(path.clone(), args.iter().map(|arg| arg.resolve(params)).collect()),
+            },
+        }
+    }
+
+    /// Calls `f` with this type, then with every type nested inside it.
+    pub fn walk(&self, f: &mut dyn FnMut(&RustType)) {
+        self.visit(&mut |ty| {
+            f(ty);
+            true
+        });
+    }
+
+    /// Calls `f` with this type, then with the types nested inside it if `f` returns true.
+    pub fn visit(&self, f: &mut dyn FnMut(&RustType) -> bool) {
+        if !f(self) {
+            return;
+        }
+        match self {
+            RustType::Vec(inner) | RustType::Array(inner, _) | RustType::Set(inner) | RustType::Option(inner) => inner.visit(f),
+            RustType::Map(first, second) | RustType::Result(first, second) => {
+                first.visit(f);
+                second.visit(f);
+            }
+            RustType::Tuple(elems) | RustType::Other(_, elems) => elems.iter().for_each(|elem| elem.visit(f)),
+        }
+    }
 }
 
 /// Definition of enums in Rust
@@ -106,24 +415,73 @@ pub enum RustEnum {
     Algebraic(RustAlgebraicEnum),
 }
 
+impl RustEnum {
+    pub fn id(&self) -> &Id {
+        match self {
+            RustEnum::Constant(e) => &e.id,
+            RustEnum::Algebraic(e) => &e.id,
+        }
+    }
+}
+
 /// Definition of constant enums.
 pub struct RustConstEnum {
     pub id: Id,
     pub comments: Vec<String>,
     pub ty: Option<syn::Lit>,
     pub cases: Vec<RustConst>,
+    /// `#[non_exhaustive]`, or has a `#[serde(other)]` case: clients should accept values added
+    /// after they were generated instead of failing.
+    pub accepts_unknown: bool,
+    /// The `#[repr(...)]` integer type of an enum derived with `serde_repr`, which is serialized as
+    /// its discriminant rather than its name.
+    pub repr: Option<String>,
 }
 
 pub struct RustConst {
     pub id: Id,
     pub comments: Vec<String>,
     pub value: Option<syn::ExprLit>,
+    /// `#[serde(other)]`: the case Rust deserializes unknown values into.
+    pub is_other: bool,
+    /// The discriminant of a `serde_repr` enum case, explicit or counted up from the previous case.
+    pub repr_value: Option<i128>,
 }
 
 pub struct RustAlgebraicEnum {
     pub id: Id,
     pub comments: Vec<String>,
     pub cases: Vec<RustAlgebraicEnumCase>,
+    pub tagging: EnumTagging,
+}
+
+/// How serde marks which variant of an algebraic enum a value is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EnumTagging {
+    /// serde's default: `{"Variant": value}`.
+    External,
+    /// `#[serde(tag = "t")]`: `{"t": "Variant", ...}`, with the variant's fields beside the tag.
+    Internal { tag: String },
+    /// `#[serde(tag = "t", content = "c")]`: `{"t": "Variant", "c": value}`.
+    Adjacent { tag: String, content: String },
+    /// `#[serde(untagged)]`: just the value, told apart by its shape.
+    Untagged,
+}
+
+impl EnumTagging {
+    fn from_attrs(attrs: &[syn::Attribute]) -> Self {
+        const SUFFIX: &str = r##"""##;
+        if serde_flag(attrs, "untagged") {
+            return EnumTagging::Untagged;
+        }
+        let tag = attr_values(attrs, r##"tag = ""##, SUFFIX).into_iter().next();
+        let content = attr_values(attrs, r##"content = ""##, SUFFIX).into_iter().next();
+        match (tag, content) {
+            (Some(tag), Some(content)) => EnumTagging::Adjacent { tag, content },
+            (Some(tag), None) => EnumTagging::Internal { tag },
+            _ => EnumTagging::External,
+        }
+    }
 }
 
 pub struct RustAlgebraicEnumCase {
@@ -141,8 +499,24 @@ pub trait Language {
         Ok(())
     }
 
+    /// Why values of `ty`, a type path such as `u64`, may not survive the trip through this
+    /// language, or `None` if they do.
+    fn lossy_type(&self, _ty: &str, _params: &Params) -> Option<String> {
+        None
+    }
+
+    /// The type `mapping` gives for this language, from a config file or `#[typeshare(...)]`.
+    fn mapped_type<'a>(&self, _mapping: &'a TypeMapping) -> Option<&'a str> {
+        None
+    }
+
     fn write_struct(&mut self, w: &mut dyn Write, params: &Params, rs: &RustStruct) -> std::io::Result<()>;
 
+    /// Writes a `#[serde(transparent)]` struct, which has exactly one field.
+    fn write_transparent_struct(&mut self, w: &mut dyn Write, params: &Params, rs: &RustStruct) -> std::io::Result<()> {
+        self.write_struct(w, params, rs)
+    }
+
     fn write_const_enum(&mut self, _w: &mut dyn Write, _params: &Params, _e: &RustConstEnum) -> std::io::Result<()> {
         Ok(())
     }
@@ -152,19 +526,168 @@ pub trait Language {
     }
 }
 
+#[derive(Default)]
 pub struct Params {
     pub use_marker: bool,
     pub swift_prefix: String,
     pub java_package: String,
+    /// Emit separate `Request`/`Response` types, besides the combined type, for structs whose input
+    /// and output shapes differ.
+    pub split_directions: bool,
+    /// Emit `#[serde(transparent)]` structs as Swift wrapper types instead of type aliases.
+    pub swift_wrap_transparent: bool,
+    /// Emit runtime checks that reject unknown fields for `#[serde(deny_unknown_fields)]` structs in TypeScript.
+    pub typescript_check_unknown_fields: bool,
+    /// Type byte buffers as `Uint8Array` in TypeScript instead of their JSON form.
+    pub typescript_uint8array: bool,
+    /// Type sets as `Set` in TypeScript instead of arrays.
+    pub typescript_set: bool,
+    /// How TypeScript types 64- and 128-bit integers.
+    pub typescript_large_integers: TypeScriptLargeIntegers,
+    /// How Java types unsigned 64-bit integers.
+    pub java_large_integers: JavaLargeIntegers,
+    /// Type mappings and imports read from a config file.
+    pub config: Config,
+    /// What the enums and newtypes being written are written as when used as map keys. Filled in by
+    /// the generator.
+    #[doc(hidden)]
+    pub map_keys: BTreeMap<String, MapKey>,
+    /// Names of the types defined in the source, which shadow std and crate types of the same name.
+    /// Filled in by the generator.
+    #[doc(hidden)]
+    pub local_types: BTreeSet<String>,
+    /// The types the fields and cases of each struct and enum being written refer to, by the name
+    /// of the struct or enum. Filled in by the generator.
+    #[doc(hidden)]
+    pub type_references: BTreeMap<String, Vec<RustType>>,
+    /// Names of the structs being written as objects of named fields, rather than as the type they
+    /// wrap, and the keys Rust reads their fields from. Filled in by the generator.
+    #[doc(hidden)]
+    pub object_types: BTreeMap<String, Vec<String>>,
+}
+
+impl Params {
+    /// The type serde writes `path` like, for std and crate types it doesn't write as their own
+    /// name. A bare name defined in the source is the user's own type, and types in the config file
+    /// are left for the backends to map.
+    pub fn wire_type(&self, path: &str, args: &[RustType]) -> Option<RustType> {
+        if (!path.contains("::") && self.local_types.contains(path)) || self.config.type_mapping(path).is_some() {
+            return None;
+        }
+        match (path.rsplit("::").next()?, args) {
+            ("NonZero", [inner]) if is_std_path(path) => Some(inner.clone()),
+            ("Map", [key, value]) if cfg!(feature = "serde_json") && is_crate_path(path, "serde_json") => Some(RustType::Map(Box::new(key.clone()), Box::new(value.clone()))),
+            _ => std_wire_type(path)
+                .or_else(|| crate_wire_type(path))
+                .map(|wire_type| RustType::Other(wire_type.to_string(), Vec::new())),
+        }
+    }
+}
+
+/// What serde writes a map key as. JSON object keys are strings, so serde turns integers and unit
+/// enum variants into strings too.
+#[derive(Clone, Debug, PartialEq)]
+pub enum MapKey {
+    /// A string.
+    String,
+    /// An integer, such as `u32`, written as its digits.
+    Integer(String),
+    /// A unit-only enum, written as a variant's name, including one that accepts unknown names.
+    /// Maps keyed by it needn't hold every variant.
+    Enum(String),
+    /// Anything else.
+    Other,
+}
+
+/// Looks through `#[serde(transparent)]` newtypes to what the key is written as.
+pub fn map_key(key: &RustType, params: &Params) -> MapKey {
+    match key {
+        RustType::Other(path, args) if args.is_empty() => match path.as_str() {
+            "String" | "str" | "char" => MapKey::String,
+            "i8" | "i16" | "i32" | "i64" | "i128" | "isize" | "u8" | "u16" | "u32" | "u64" | "u128" | "usize" => MapKey::Integer(path.clone()),
+            _ => params.map_keys.get(path).cloned().unwrap_or(MapKey::Other),
+        },
+        _ => MapKey::Other,
+    }
+}
+
+/// How TypeScript types integers of 64 bits and more, beyond the 53 bits a `number` holds exactly.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum TypeScriptLargeIntegers {
+    /// `number`, which rounds values beyond 2^53.
+    #[default]
+    Number,
+    /// `bigint`, which needs a JSON parser that reads large numbers into one.
+    BigInt,
+    /// `string`, for integers Rust writes as strings, with helpers to convert them to `bigint`.
+    String,
+}
+
+impl TypeScriptLargeIntegers {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "number" => Some(TypeScriptLargeIntegers::Number),
+            "bigint" => Some(TypeScriptLargeIntegers::BigInt),
+            "string" => Some(TypeScriptLargeIntegers::String),
+            _ => None,
+        }
+    }
+}
+
+/// How Java types `u64` and `usize`. Smaller unsigned integers always widen to the next signed type.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum JavaLargeIntegers {
+    /// `long`, which can't hold values of 2^63 and above.
+    #[default]
+    Long,
+    /// `java.math.BigInteger`.
+    BigInteger,
+}
+
+impl JavaLargeIntegers {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "long" => Some(JavaLargeIntegers::Long),
+            "biginteger" => Some(JavaLargeIntegers::BigInteger),
+            _ => None,
+        }
+    }
+}
+
+/// The `rename_all` rules set on a container or variant, per direction.
+#[derive(Clone, Copy, Default)]
+struct RenameAll {
+    serialize: Option<RenameRule>,
+    deserialize: Option<RenameRule>,
+}
+
+impl RenameAll {
+    /// Reads `name = "rule"` or `name(serialize = "rule", deserialize = "rule")`.
+    fn from_attrs(attrs: &[syn::Attribute], name: &str) -> Self {
+        let (serialize, deserialize) = serde_directional_value(attrs, name);
+        RenameAll {
+            serialize: serialize.and_then(|rule| RenameRule::from_name(&rule)),
+            deserialize: deserialize.and_then(|rule| RenameRule::from_name(&rule)),
+        }
+    }
+
+    /// Falls back to `other` in each direction without a rule of its own.
+    fn or(self, other: RenameAll) -> RenameAll {
+        RenameAll {
+            serialize: self.serialize.or(other.serialize),
+            deserialize: self.deserialize.or(other.deserialize),
+        }
+    }
 }
 
 pub struct Generator<'l> {
     params: Params,
     language: &'l mut dyn Language,
-    serde_rename_all: Option<String>,
+    serde_rename_all: RenameAll,
 
     structs: Vec<RustStruct>,
     enums: Vec<RustEnum>,
+    warnings: Vec<String>,
 }
 
 impl<'l> Generator<'l> {
@@ -172,10 +695,11 @@ impl<'l> Generator<'l> {
         Self {
             params,
             language,
-            serde_rename_all: None,
+            serde_rename_all: RenameAll::default(),
 
             structs: Vec::new(),
             enums: Vec::new(),
+            warnings: Vec::new(),
         }
     }
 
@@ -189,28 +713,142 @@ impl<'l> Generator<'l> {
         let source = syn::parse_file(&source)?;
         for item in source.items.iter() {
             match item {
-                syn::Item::Struct(s) => self.parse_struct(&s)?,
-                syn::Item::Enum(e) => self.parse_enum(&e)?,
+                syn::Item::Struct(s) => self.params.local_types.insert(s.ident.to_string()),
+                syn::Item::Enum(e) => self.params.local_types.insert(e.ident.to_string()),
+                syn::Item::Type(t) => self.params.local_types.insert(t.ident.to_string()),
+                _ => false,
+            };
+        }
+        for item in source.items.iter() {
+            match item {
+                syn::Item::Struct(s) => self.parse_struct(s)?,
+                syn::Item::Enum(e) => self.parse_enum(e)?,
                 syn::Item::Fn(_) => {}
                 _ => {}
             }
         }
+        self.add_std_structs()?;
+        self.note_map_keys();
+        self.note_type_references();
 
         self.write(w)?;
         Ok(())
     }
 
+    /// Records what unit-only enums and newtypes are written as, for maps keyed by them.
+    fn note_map_keys(&mut self) {
+        for e in self.enums.iter() {
+            if let RustEnum::Constant(e) = e {
+                self.params.map_keys.insert(e.id.original.clone(), MapKey::Enum(e.id.original.clone()));
+            }
+        }
+        for rs in self.structs.iter().filter(|rs| rs.is_transparent) {
+            let key = map_key(&rs.fields[0].rust_type(&self.params), &self.params);
+            self.params.map_keys.insert(rs.id.original.clone(), key);
+        }
+    }
+
+    /// Records the types each struct and enum refers to, and which structs are objects, for backends
+    /// that follow them.
+    fn note_type_references(&mut self) {
+        let mut references = BTreeMap::new();
+        for rs in self.structs.iter() {
+            let types = rs.fields.iter().map(|rf| rf.rust_type(&self.params)).collect();
+            references.insert(rs.id.original.clone(), types);
+        }
+        for e in self.enums.iter() {
+            if let RustEnum::Algebraic(e) = e {
+                let types = e.cases.iter().map(|c| c.value.rust_type(&self.params)).collect();
+                references.insert(e.id.original.clone(), types);
+            }
+        }
+        self.params.type_references = references;
+        self.params.object_types = self
+            .structs
+            .iter()
+            .filter(|rs| !rs.is_transparent)
+            .map(|rs| (rs.id.original.clone(), self.read_keys(rs, &mut Vec::new())))
+            .collect();
+    }
+
+    /// The keys Rust reads the fields of a struct from, including those of the structs it flattens.
+    fn read_keys(&self, rs: &RustStruct, visited: &mut Vec<String>) -> Vec<String> {
+        visited.push(rs.id.original.clone());
+        let mut keys: Vec<String> = Vec::new();
+        for f in rs.fields.iter().filter(|f| !f.is_catch_all()) {
+            let field_keys = if !f.is_flattened {
+                std::iter::once(&f.id.renamed_deserialize).chain(f.id.aliases.iter()).cloned().collect()
+            } else {
+                match RustType::parse(&f.ty).resolve(&self.params) {
+                    RustType::Other(path, _) if !visited.contains(&path) => match self.structs.iter().find(|s| s.id.original == path) {
+                        Some(flattened) => self.read_keys(flattened, visited),
+                        None => Vec::new(),
+                    },
+                    _ => Vec::new(),
+                }
+            };
+            for key in field_keys {
+                if !keys.contains(&key) {
+                    keys.push(key);
+                }
+            }
+        }
+        keys
+    }
+
+    /// Adds the structs for std types written as objects, such as `Duration`, that are referred to
+    /// but not defined in the source.
+    fn add_std_structs(&mut self) -> std::io::Result<()> {
+        let mut referenced = Vec::new();
+        let fields = self.structs.iter().flat_map(|rs| rs.fields.iter());
+        let cases = self.enums.iter().flat_map(|e| match e {
+            RustEnum::Algebraic(e) => e.cases.iter().map(|c| &c.value).collect(),
+            RustEnum::Constant(_) => Vec::new(),
+        });
+        for rf in fields.chain(cases) {
+            rf.rust_type(&self.params).walk(&mut |ty| {
+                if let RustType::Other(path, _) = ty {
+                    referenced.push(path.clone());
+                }
+            });
+        }
+
+        for (name, source) in STD_STRUCTS {
+            if referenced.iter().any(|path| path == name) && !self.structs.iter().any(|rs| rs.id.original == *name) {
+                let item: syn::ItemStruct = syn::parse_str(source).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string()))?;
+                self.parse_struct(&item)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Problems found with the types written, such as integers the target language can't hold.
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
     pub fn write(&mut self, w: &mut dyn Write) -> Result<(), Box<dyn Error>> {
+        self.warn_lossy_types();
         self.language.begin_file(w, &self.params)?;
 
         for s in &self.structs {
-            self.language.write_struct(w, &self.params, &s)?;
+            if self.is_mapped(&s.id.original) {
+                continue;
+            }
+            if s.is_transparent {
+                self.language.write_transparent_struct(w, &self.params, s)?;
+            } else {
+                self.language.write_struct(w, &self.params, s)?;
+            }
         }
 
         for e in &self.enums {
+            if self.is_mapped(&e.id().original) {
+                continue;
+            }
             match e {
-                RustEnum::Constant(const_enum) => self.language.write_const_enum(w, &self.params, &const_enum)?,
-                RustEnum::Algebraic(algebraic_enum) => self.language.write_algebraic_enum(w, &self.params, &algebraic_enum)?,
+                RustEnum::Constant(const_enum) => self.language.write_const_enum(w, &self.params, const_enum)?,
+                RustEnum::Algebraic(algebraic_enum) => self.language.write_algebraic_enum(w, &self.params, algebraic_enum)?,
             }
         }
 
@@ -218,51 +856,205 @@ impl<'l> Generator<'l> {
         Ok(())
     }
 
+    /// Whether the type named `name` is given for this language, in the config file or with
+    /// `#[typeshare(...)]`. It's written as that type wherever it's referred to, so isn't defined.
+    fn is_mapped(&self, name: &str) -> bool {
+        self.params.config.type_mapping(name).and_then(|m| self.language.mapped_type(m)).is_some()
+    }
+
+    fn warn_lossy_types(&mut self) {
+        let fields = self
+            .structs
+            .iter()
+            .filter(|rs| !self.is_mapped(&rs.id.original))
+            .flat_map(|rs| rs.fields.iter().map(move |rf| (format!("{}.{}", rs.id.original, rf.id.original), rf)));
+        let cases = self.enums.iter().filter(|e| !self.is_mapped(&e.id().original)).flat_map(|e| match e {
+            RustEnum::Algebraic(e) => e.cases.iter().map(|c| (format!("{}::{}", e.id.original, c.id.original), &c.value)).collect(),
+            RustEnum::Constant(_) => Vec::new(),
+        });
+
+        let mut warnings = Vec::new();
+        for (name, rf) in fields.chain(cases) {
+            // Types given for this language are written as they are, whatever they hold.
+            if self.language.mapped_type(&rf.type_override).is_some() {
+                continue;
+            }
+            let mut reasons = Vec::new();
+            rf.rust_type(&self.params).visit(&mut |ty| match ty {
+                RustType::Other(path, _) if self.params.config.type_mapping(path).and_then(|m| self.language.mapped_type(m)).is_some() => false,
+                RustType::Other(path, _) => {
+                    reasons.extend(self.language.lossy_type(path, &self.params));
+                    true
+                }
+                _ => true,
+            });
+            reasons.dedup();
+            warnings.extend(reasons.into_iter().map(|reason| format!("{}: {}", name, reason)));
+        }
+        self.warnings = warnings;
+    }
+
     fn parse_struct(&mut self, s: &syn::ItemStruct) -> std::io::Result<()> {
         if self.params.use_marker && !has_typeshare_marker(&s.attrs) {
             return Ok(());
         }
 
-        self.serde_rename_all = serde_rename_all(&s.attrs);
+        self.note_type_override(&s.ident, &s.attrs);
+        if let Some(serialized_as) = typeshare_value(&s.attrs, "serialized_as") {
+            let id = get_ident(Some(&s.ident), &s.attrs, &RenameAll::default(), RenameRule::apply_to_field);
+            let mut comments = Vec::new();
+            self.parse_comment_attrs(&mut comments, &s.attrs)?;
+            let rs = self.proxy_struct(&id, &comments, &serialized_as)?;
+            self.structs.push(rs);
+            return Ok(());
+        }
+
+        self.serde_rename_all = RenameAll::from_attrs(&s.attrs, "rename_all");
 
         let mut rs = RustStruct {
-            id: get_ident(Some(&s.ident), &s.attrs, &self.serde_rename_all),
+            id: get_ident(Some(&s.ident), &s.attrs, &RenameAll::default(), RenameRule::apply_to_field),
             fields: Vec::new(),
             comments: Vec::new(),
+            direction: Direction::Both,
+            has_default: serde_flag(&s.attrs, "default"),
+            is_transparent: false,
+            deny_unknown_fields: serde_flag(&s.attrs, "deny_unknown_fields"),
         };
         self.parse_comment_attrs(&mut rs.comments, &s.attrs)?;
 
         for f in s.fields.iter() {
-            self.parse_field(&mut rs, &f)?;
+            self.parse_field(&mut rs, f)?;
         }
 
-        self.serde_rename_all = None;
-        self.structs.push(rs);
+        // serde only accepts `transparent` with a single field left after skipped ones.
+        rs.is_transparent = serde_flag(&s.attrs, "transparent") && rs.fields.len() == 1;
+
+        self.serde_rename_all = RenameAll::default();
+        let (into, from) = serde_proxy_types(&s.attrs);
+        if into.is_some() || from.is_some() {
+            return self.push_proxied_struct(rs, into, from);
+        }
+        // Other types keep referring to the combined struct, so it's written alongside its halves.
+        if self.params.split_directions && rs.differs_by_direction() {
+            let (input, output) = (rs.for_direction(Direction::Input), rs.for_direction(Direction::Output));
+            self.structs.extend([rs, input, output]);
+        } else {
+            self.structs.push(rs);
+        }
+        Ok(())
+    }
+
+    /// A struct with `into` or `from`/`try_from` is sent as the proxy type in that direction, and
+    /// as its own fields in the other. Without split directions, clients get the serialized shape.
+    fn push_proxied_struct(&mut self, rs: RustStruct, into: Option<String>, from: Option<String>) -> std::io::Result<()> {
+        let output = match &into {
+            Some(proxy) => self.proxy_struct(&rs.id, &rs.comments, proxy)?,
+            None => rs.clone(),
+        };
+        let input = match &from {
+            Some(proxy) => self.proxy_struct(&rs.id, &rs.comments, proxy)?,
+            None => rs,
+        };
+
+        if self.params.split_directions && into != from {
+            let (input, output_half) = (input.for_direction(Direction::Input), output.for_direction(Direction::Output));
+            self.structs.extend([output, input, output_half]);
+        } else {
+            self.structs.push(output);
+        }
         Ok(())
     }
 
+    /// Types given with `#[typeshare(ts = "...", swift = "...", java = "...")]` are written wherever
+    /// the type is referred to, over any from the config file.
+    fn note_type_override(&mut self, ident: &syn::Ident, attrs: &[syn::Attribute]) {
+        let type_override = typeshare_type_mapping(attrs);
+        if type_override.ts.is_none() && type_override.swift.is_none() && type_override.java.is_none() {
+            return;
+        }
+        let mapping = self.params.config.types.entry(ident.to_string()).or_default();
+        mapping.ts = type_override.ts.or_else(|| mapping.ts.take());
+        mapping.swift = type_override.swift.or_else(|| mapping.swift.take());
+        mapping.java = type_override.java.or_else(|| mapping.java.take());
+    }
+
+    /// Builds a transparent struct wrapping `proxy`, so every backend emits the type as the proxy.
+    fn proxy_struct(&mut self, id: &Id, comments: &[String], proxy: &str) -> std::io::Result<RustStruct> {
+        let ty = parse_type(proxy)?;
+        let mut rs = RustStruct {
+            id: id.clone(),
+            fields: Vec::new(),
+            comments: comments.to_vec(),
+            direction: Direction::Both,
+            has_default: false,
+            is_transparent: true,
+            deny_unknown_fields: false,
+        };
+        let field = syn::Field {
+            attrs: Vec::new(),
+            vis: syn::Visibility::Inherited,
+            ident: None,
+            colon_token: None,
+            ty,
+        };
+        self.parse_field(&mut rs, &field)?;
+        Ok(rs)
+    }
+
     fn parse_field(&mut self, rs: &mut RustStruct, f: &syn::Field) -> std::io::Result<()> {
-        let mut ty: &str = &type_as_string(&f.ty);
+        if serde_flag(&f.attrs, "skip") {
+            return Ok(());
+        }
+        let direction = match (serde_flag(&f.attrs, "skip_serializing"), serde_flag(&f.attrs, "skip_deserializing")) {
+            (true, true) => return Ok(()),
+            (true, false) => Direction::Input,
+            (false, true) => Direction::Output,
+            (false, false) => Direction::Both,
+        };
+
+        let wire_type = match typeshare_value(&f.attrs, "serialized_as") {
+            Some(serialized_as) => Some(parse_type(&serialized_as)?),
+            None => serde_as_type(&f.attrs, &f.ty),
+        };
+        let mut ty: &str = &type_as_string(&unwrap_type(wire_type.as_ref().unwrap_or(&f.ty)));
         let is_optional = ty.starts_with(OPTION_PREFIX);
         if is_optional {
-            ty = remove_prefix_suffix(&ty, OPTION_PREFIX, OPTION_SUFFIX);
+            ty = remove_prefix_suffix(ty, OPTION_PREFIX, OPTION_SUFFIX);
+        }
+        let is_double_option = is_optional && ty.starts_with(OPTION_PREFIX);
+        if is_double_option {
+            ty = remove_prefix_suffix(ty, OPTION_PREFIX, OPTION_SUFFIX);
         }
 
-        let is_vec = ty.starts_with(VEC_PREFIX);
-        let is_hash_map = ty.starts_with(HASH_MAP_PREFIX);
-        if is_vec {
-            ty = &remove_prefix_suffix(&ty, VEC_PREFIX, VEC_SUFFIX);
-        } else if is_hash_map {
-            ty = &remove_prefix_suffix(&ty, HASH_MAP_PREFIX, HASH_MAP_SUFFIX);
+        let bytes = bytes_encoding(&f.attrs, ty);
+        let (is_vec, is_hash_map) = match RustType::parse(ty).resolve(&self.params) {
+            RustType::Vec(_) => (bytes.is_none(), false),
+            RustType::Map(..) => (false, true),
+            _ => (false, false),
+        };
+        let fixed_len = match RustType::parse(ty) {
+            RustType::Array(_, len) => Some(len),
+            _ => None,
+        };
+        if is_vec || is_hash_map {
+            ty = generic_args(ty);
         }
 
         let mut rf = RustField {
-            id: get_ident(f.ident.as_ref(), &f.attrs, &self.serde_rename_all),
+            id: get_ident(f.ident.as_ref(), &f.attrs, &self.serde_rename_all, RenameRule::apply_to_field),
             ty: ty.to_owned(),
             is_optional,
+            is_double_option,
             is_vec,
             is_hash_map,
             comments: Vec::new(),
+            direction,
+            has_default: rs.has_default || serde_flag(&f.attrs, "default"),
+            is_flattened: serde_flag(&f.attrs, "flatten"),
+            skip_serializing_if: serde_flag(&f.attrs, "skip_serializing_if"),
+            bytes,
+            type_override: typeshare_type_mapping(&f.attrs),
+            fixed_len,
         };
         self.parse_comment_attrs(&mut rf.comments, &f.attrs)?;
 
@@ -275,33 +1067,63 @@ impl<'l> Generator<'l> {
             return Ok(());
         }
 
-        self.serde_rename_all = serde_rename_all(&e.attrs);
+        // Enums converted to another type are emitted as that type, whichever direction names it.
+        self.note_type_override(&e.ident, &e.attrs);
+        let (into, from) = serde_proxy_types(&e.attrs);
+        if let Some(proxy) = typeshare_value(&e.attrs, "serialized_as").or(into).or(from) {
+            let id = get_ident(Some(&e.ident), &e.attrs, &RenameAll::default(), RenameRule::apply_to_variant);
+            let mut comments = Vec::new();
+            self.parse_comment_attrs(&mut comments, &e.attrs)?;
+            let rs = self.proxy_struct(&id, &comments, &proxy)?;
+            self.structs.push(rs);
+            return Ok(());
+        }
+
+        self.serde_rename_all = RenameAll::from_attrs(&e.attrs, "rename_all");
         if is_const_enum(e) {
             self.parse_const_enum(e)?;
         } else {
             self.parse_algebraic_enum(e)?;
         }
-        self.serde_rename_all = None;
+        self.serde_rename_all = RenameAll::default();
         Ok(())
     }
 
     fn parse_const_enum(&mut self, e: &syn::ItemEnum) -> std::io::Result<()> {
         let mut re = RustConstEnum {
-            id: get_ident(Some(&e.ident), &e.attrs, &self.serde_rename_all),
+            id: get_ident(Some(&e.ident), &e.attrs, &RenameAll::default(), RenameRule::apply_to_variant),
             comments: Vec::new(),
             ty: get_const_enum_type(e).clone(),
             cases: Vec::new(),
+            accepts_unknown: e.attrs.iter().any(|a| a.path.is_ident("non_exhaustive")),
+            repr: serde_repr_type(&e.attrs),
         };
         self.parse_comment_attrs(&mut re.comments, &e.attrs)?;
 
+        // Skipped variants still take up a discriminant.
+        let mut next_repr_value = 0;
         for v in e.variants.iter() {
+            let repr_value = if re.repr.is_some() {
+                let value = repr_discriminant(v).unwrap_or(next_repr_value);
+                next_repr_value = value.wrapping_add(1);
+                Some(value)
+            } else {
+                None
+            };
+            if serde_flag(&v.attrs, "skip") {
+                continue;
+            }
+
             let mut rc = RustConst {
-                id: get_ident(Some(&v.ident), &v.attrs, &self.serde_rename_all),
-                value: get_discriminant(&v),
+                id: get_ident(Some(&v.ident), &v.attrs, &self.serde_rename_all, RenameRule::apply_to_variant),
+                value: if re.repr.is_some() { None } else { get_discriminant(v) },
                 comments: Vec::new(),
+                is_other: serde_flag(&v.attrs, "other"),
+                repr_value,
             };
 
             self.parse_comment_attrs(&mut rc.comments, &v.attrs)?;
+            re.accepts_unknown |= rc.is_other;
             re.cases.push(rc);
         }
 
@@ -312,16 +1134,23 @@ impl<'l> Generator<'l> {
 
     fn parse_algebraic_enum(&mut self, e: &syn::ItemEnum) -> std::io::Result<()> {
         let mut parsed_enum = RustAlgebraicEnum {
-            id: get_ident(Some(&e.ident), &e.attrs, &self.serde_rename_all),
+            id: get_ident(Some(&e.ident), &e.attrs, &RenameAll::default(), RenameRule::apply_to_variant),
             comments: Vec::new(),
             cases: Vec::new(),
+            tagging: EnumTagging::from_attrs(&e.attrs),
         };
         self.parse_comment_attrs(&mut parsed_enum.comments, &e.attrs)?;
 
-        for variant in e.variants.iter() {
+        let rename_all_fields = RenameAll::from_attrs(&e.attrs, "rename_all_fields");
+        // A `#[serde(other)]` unit variant has no type of its own to add to the union.
+        for variant in e.variants.iter().filter(|v| !serde_flag(&v.attrs, "skip") && !serde_flag(&v.attrs, "other")) {
+            let value = match &variant.fields {
+                syn::Fields::Named(fields) => self.parse_struct_variant(e, variant, fields, rename_all_fields)?,
+                _ => get_algebraic_enum_case_value(variant, &self.serde_rename_all, &self.params),
+            };
             let mut parsed_case = RustAlgebraicEnumCase {
-                id: get_ident(Some(&variant.ident), &variant.attrs, &self.serde_rename_all),
-                value: get_algebraic_enum_case_value(&variant, &self.serde_rename_all),
+                id: get_ident(Some(&variant.ident), &variant.attrs, &self.serde_rename_all, RenameRule::apply_to_variant),
+                value,
                 comments: Vec::new(),
             };
             self.parse_comment_attrs(&mut parsed_case.comments, &variant.attrs)?;
@@ -333,6 +1162,53 @@ impl<'l> Generator<'l> {
         Ok(())
     }
 
+    /// Parses the fields of a struct variant into a separate `{Enum}{Variant}` struct, and returns
+    /// the case value referring to it. The variant's own `rename_all` takes precedence over the
+    /// enum's `rename_all_fields`.
+    fn parse_struct_variant(&mut self, e: &syn::ItemEnum, v: &syn::Variant, fields: &syn::FieldsNamed, rename_all_fields: RenameAll) -> std::io::Result<RustField> {
+        let name = format!("{}{}", e.ident, v.ident);
+        let mut rs = RustStruct {
+            id: Id {
+                original: name.clone(),
+                renamed: name.clone(),
+                renamed_deserialize: name.clone(),
+                aliases: Vec::new(),
+            },
+            fields: Vec::new(),
+            comments: Vec::new(),
+            direction: Direction::Both,
+            has_default: false,
+            is_transparent: false,
+            // On an enum, serde applies this to the fields of every struct variant.
+            deny_unknown_fields: serde_flag(&e.attrs, "deny_unknown_fields"),
+        };
+
+        let variant_rename_all = self.serde_rename_all;
+        self.serde_rename_all = RenameAll::from_attrs(&v.attrs, "rename_all").or(rename_all_fields);
+        for f in fields.named.iter() {
+            self.parse_field(&mut rs, f)?;
+        }
+        self.serde_rename_all = variant_rename_all;
+        self.structs.push(rs);
+
+        Ok(RustField {
+            id: get_ident(Some(&v.ident), &v.attrs, &self.serde_rename_all, RenameRule::apply_to_variant),
+            ty: name,
+            is_optional: false,
+            is_double_option: false,
+            is_vec: false,
+            is_hash_map: false,
+            comments: Vec::new(),
+            direction: Direction::Both,
+            has_default: false,
+            is_flattened: false,
+            skip_serializing_if: false,
+            bytes: None,
+            type_override: TypeMapping::default(),
+            fixed_len: None,
+        })
+    }
+
     //----
 
     fn parse_comment_attrs(&mut self, comments: &mut Vec<String>, attrs: &[syn::Attribute]) -> std::io::Result<()> {
@@ -362,36 +1238,72 @@ fn get_discriminant(v: &syn::Variant) -> Option<syn::ExprLit> {
     None
 }
 
-fn get_algebraic_enum_case_value(v: &syn::Variant, serde_rename_all: &Option<String>) -> RustField {
-    match &v.fields {
-        syn::Fields::Unnamed(associated_type) => {
-            if associated_type.unnamed.len() > 1 {
-                panic!("Unable to handle multiple unamed associated types yet");
-            }
+/// Reads an integer discriminant, which may be negative.
+fn repr_discriminant(v: &syn::Variant) -> Option<i128> {
+    match &v.discriminant.as_ref()?.1 {
+        syn::Expr::Lit(syn::ExprLit { lit, .. }) => int_literal(lit),
+        syn::Expr::Unary(syn::ExprUnary { op: syn::UnOp::Neg(_), expr, .. }) => match &**expr {
+            syn::Expr::Lit(syn::ExprLit { lit, .. }) => int_literal(lit).map(|value| -value),
+            _ => None,
+        },
+        _ => None,
+    }
+}
 
-            let first_type = associated_type.unnamed.clone().first().unwrap().into_value().ty.clone();
+/// The value of an integer literal. syn only reads those up to `u64::MAX`, so wider decimal ones
+/// are read from their tokens.
+fn int_literal(lit: &syn::Lit) -> Option<i128> {
+    match lit {
+        syn::Lit::Int(i) => Some(i128::from(i.value())),
+        syn::Lit::Verbatim(v) => {
+            let digits: String = v.token.to_string().chars().take_while(|c| c.is_ascii_digit() || *c == '_').filter(|c| *c != '_').collect();
+            digits.parse().ok()
+        }
+        _ => None,
+    }
+}
 
-            let mut ty: &str = &type_as_string(&first_type);
+fn get_algebraic_enum_case_value(v: &syn::Variant, serde_rename_all: &RenameAll, params: &Params) -> RustField {
+    match &v.fields {
+        syn::Fields::Unnamed(associated_type) => {
+            let mut types: Vec<String> = associated_type
+                .unnamed
+                .iter()
+                .map(|f| type_as_string(&unwrap_type(&serde_as_type(&f.attrs, &f.ty).unwrap_or_else(|| f.ty.clone()))))
+                .collect();
+            // Variants with several fields are written like a tuple of them.
+            let case_type = if types.len() == 1 { types.remove(0) } else { format!("( {} )", types.join(" , ")) };
+
+            let mut ty: &str = &case_type;
             let is_optional = ty.starts_with(OPTION_PREFIX);
             if is_optional {
-                ty = remove_prefix_suffix(&ty, OPTION_PREFIX, OPTION_SUFFIX);
+                ty = remove_prefix_suffix(ty, OPTION_PREFIX, OPTION_SUFFIX);
             }
 
-            let is_vec = ty.starts_with(VEC_PREFIX);
-            let is_hash_map = ty.starts_with(HASH_MAP_PREFIX);
-            if is_vec {
-                ty = &remove_prefix_suffix(&ty, VEC_PREFIX, VEC_SUFFIX);
-            } else if is_hash_map {
-                ty = &remove_prefix_suffix(&ty, HASH_MAP_PREFIX, HASH_MAP_SUFFIX);
+            let (is_vec, is_hash_map) = match RustType::parse(ty).resolve(params) {
+                RustType::Vec(_) => (true, false),
+                RustType::Map(..) => (false, true),
+                _ => (false, false),
+            };
+            if is_vec || is_hash_map {
+                ty = generic_args(ty);
             }
 
             RustField {
-                id: get_ident(Some(&v.ident), &v.attrs, serde_rename_all),
+                id: get_ident(Some(&v.ident), &v.attrs, serde_rename_all, RenameRule::apply_to_variant),
                 ty: ty.to_owned(),
                 is_optional,
+                is_double_option: false,
                 is_vec,
                 is_hash_map,
                 comments: Vec::new(),
+                direction: Direction::Both,
+                has_default: false,
+                is_flattened: false,
+                skip_serializing_if: false,
+                bytes: None,
+                type_override: TypeMapping::default(),
+                fixed_len: None,
             }
         }
         _ => panic!("Call this method for Unnamed cases only"),
@@ -401,10 +1313,7 @@ fn get_algebraic_enum_case_value(v: &syn::Variant, serde_rename_all: &Option<Str
 fn is_const_enum(e: &syn::ItemEnum) -> bool {
     for v in e.variants.iter() {
         match v.fields {
-            syn::Fields::Named(_) => {
-                panic!("Can't handle complex enum");
-            }
-            syn::Fields::Unnamed(_) => return false,
+            syn::Fields::Named(_) | syn::Fields::Unnamed(_) => return false,
             syn::Fields::Unit => {}
         }
     }
@@ -424,6 +1333,206 @@ fn get_const_enum_type(e: &syn::ItemEnum) -> Option<syn::Lit> {
     None
 }
 
+/// Applies a `serde_with` `#[serde_as(as = "...")]` adapter to a field type, giving a type with the
+/// same JSON representation as the adapter writes.
+fn serde_as_type(attrs: &[syn::Attribute], ty: &syn::Type) -> Option<syn::Type> {
+    let adapter: syn::Type = syn::parse_str(&serde_as_adapter(attrs)?).ok()?;
+    Some(apply_serde_as(&adapter, ty))
+}
+
+/// Reads `name = "..."` from `#[typeshare(...)]`.
+fn typeshare_value(attrs: &[syn::Attribute], name: &str) -> Option<String> {
+    const SUFFIX: &str = r##"""##;
+    let prefix = format!(r##"{} = ""##, name);
+    attrs.iter().filter(|a| is_typeshare_attr(a)).find_map(|a| {
+        let attr_as_string = a.tts.to_string();
+        let values = parse_attr(&attr_as_string)?;
+        let value = values.into_iter().find(|v| v.starts_with(&prefix) && v.ends_with(SUFFIX))?;
+        Some(value[prefix.len()..value.len() - SUFFIX.len()].to_string())
+    })
+}
+
+fn typeshare_type_mapping(attrs: &[syn::Attribute]) -> TypeMapping {
+    TypeMapping {
+        ts: typeshare_value(attrs, "ts"),
+        swift: typeshare_value(attrs, "swift"),
+        java: typeshare_value(attrs, "java"),
+    }
+}
+
+/// Parses a Rust type named in an attribute, such as a proxy type.
+fn parse_type(ty: &str) -> std::io::Result<syn::Type> {
+    syn::parse_str(ty).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e.to_string()))
+}
+
+/// Reads the adapter from `#[serde_as(as = "...")]`.
+fn serde_as_adapter(attrs: &[syn::Attribute]) -> Option<String> {
+    const PREFIX: &str = r##"as = ""##;
+    const SUFFIX: &str = r##"""##;
+    attrs.iter().filter(|a| a.path.is_ident("serde_as")).find_map(|a| {
+        let attr_as_string = a.tts.to_string();
+        let values = parse_attr(&attr_as_string)?;
+        let value = values.into_iter().find(|v| v.starts_with(PREFIX) && v.ends_with(SUFFIX))?;
+        Some(remove_prefix_suffix(value, PREFIX, SUFFIX).to_string())
+    })
+}
+
+/// Recognises a byte buffer field from its type, or from a `serde_bytes` or base64 adapter.
+fn bytes_encoding(attrs: &[syn::Attribute], ty: &str) -> Option<BytesEncoding> {
+    const BYTES_TYPES: &[&str] = &[
+        "Vec < u8 >",
+        "Bytes",
+        "bytes :: Bytes",
+        "BytesMut",
+        "bytes :: BytesMut",
+        "ByteBuf",
+        "serde_bytes :: ByteBuf",
+    ];
+    let with = attr_values(attrs, r##"with = ""##, r##"""##);
+    let is_base64_adapter = serde_as_adapter(attrs).is_some_and(|adapter| adapter.rsplit("::").next().unwrap_or_default().trim().starts_with("Base64"));
+
+    if is_base64_adapter || with.iter().any(|module| module.contains("base64")) {
+        Some(BytesEncoding::Base64)
+    } else if BYTES_TYPES.contains(&ty) || ty.starts_with("[ u8 ;") || with.iter().any(|module| module == "serde_bytes") {
+        Some(BytesEncoding::Array)
+    } else {
+        None
+    }
+}
+
+/// Walks an adapter and the type it applies to together. `_` and unknown adapters keep the
+/// original type; containers such as `Vec<DisplayFromStr>` apply their arguments to the type's.
+fn apply_serde_as(adapter: &syn::Type, ty: &syn::Type) -> syn::Type {
+    let segment = match adapter {
+        syn::Type::Path(p) => match p.path.segments.iter().last() {
+            Some(segment) => segment,
+            None => return ty.clone(),
+        },
+        _ => return ty.clone(),
+    };
+    let adapter_args: Vec<&syn::Type> = match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) => args
+            .args
+            .iter()
+            .filter_map(|arg| match arg {
+                syn::GenericArgument::Type(t) => Some(t),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+    let first_arg = adapter_args.first().map(|arg| type_as_string(arg));
+
+    let json_type = match segment.ident.to_string().as_str() {
+        "Same" => return ty.clone(),
+        // These wrap another adapter, or convert through another type.
+        "DefaultOnNull" | "DefaultOnError" => return adapter_args.first().map_or_else(|| ty.clone(), |arg| apply_serde_as(arg, ty)),
+        "FromInto" | "TryFromInto" => return adapter_args.first().map_or_else(|| ty.clone(), |arg| (*arg).clone()),
+        "DisplayFromStr" | "Base64" | "Base32" | "Hex" | "StringWithSeparator" | "JsonString" | "NoneAsEmptyString" => "String",
+        "BoolFromInt" => "u8",
+        // Timestamps default to `i64`, since they may be before the epoch, and durations to `u64`.
+        "TimestampSeconds" | "TimestampMilliSeconds" | "TimestampMicroSeconds" | "TimestampNanoSeconds" => match first_arg.as_deref() {
+            Some("String") => "String",
+            Some("f64") => "f64",
+            _ => "i64",
+        },
+        "DurationSeconds" | "DurationMilliSeconds" | "DurationMicroSeconds" | "DurationNanoSeconds" => match first_arg.as_deref() {
+            Some("String") => "String",
+            Some("f64") => "f64",
+            Some("i64") => "i64",
+            _ => "u64",
+        },
+        "TimestampSecondsWithFrac"
+        | "TimestampMilliSecondsWithFrac"
+        | "TimestampMicroSecondsWithFrac"
+        | "TimestampNanoSecondsWithFrac"
+        | "DurationSecondsWithFrac"
+        | "DurationMilliSecondsWithFrac"
+        | "DurationMicroSecondsWithFrac"
+        | "DurationNanoSecondsWithFrac" => match first_arg.as_deref() {
+            Some("String") => "String",
+            _ => "f64",
+        },
+        _ => {
+            let mut ty = ty.clone();
+            if let syn::Type::Path(p) = &mut ty {
+                if let Some(syn::PathArguments::AngleBracketed(args)) = p.path.segments.iter_mut().last().map(|s| &mut s.arguments) {
+                    let type_args = args.args.iter_mut().filter_map(|arg| match arg {
+                        syn::GenericArgument::Type(t) => Some(t),
+                        _ => None,
+                    });
+                    for (arg, adapter_arg) in type_args.zip(adapter_args) {
+                        *arg = apply_serde_as(adapter_arg, arg);
+                    }
+                }
+            }
+            return ty;
+        }
+    };
+    syn::parse_str(json_type).unwrap_or_else(|_| ty.clone())
+}
+
+/// Strips what serializes like its contents: `Box`, `Rc`, `Arc`, `Cow`, references and lifetimes.
+/// Borrowed `str` and slices become their owned `String` and `Vec`.
+fn unwrap_type(ty: &syn::Type) -> syn::Type {
+    const WRAPPERS: &[&str] = &["Box", "Rc", "Arc", "Cow"];
+
+    match ty {
+        syn::Type::Reference(r) => unwrap_type(&r.elem),
+        syn::Type::Paren(p) => unwrap_type(&p.elem),
+        syn::Type::Group(g) => unwrap_type(&g.elem),
+        syn::Type::Slice(s) => {
+            let vec = format!("Vec < {} >", type_as_string(&unwrap_type(&s.elem)));
+            syn::parse_str(&vec).unwrap_or_else(|_| ty.clone())
+        }
+        syn::Type::Array(a) => {
+            let mut a = a.clone();
+            a.elem = Box::new(unwrap_type(&a.elem));
+            syn::Type::Array(a)
+        }
+        syn::Type::Tuple(t) => {
+            let mut t = t.clone();
+            t.elems = t.elems.iter().map(unwrap_type).collect();
+            syn::Type::Tuple(t)
+        }
+        syn::Type::Path(p) if p.qself.is_none() && p.path.is_ident("str") => syn::parse_str("String").unwrap_or_else(|_| ty.clone()),
+        syn::Type::Path(p) => {
+            let mut p = p.clone();
+            let last = match p.path.segments.iter_mut().last() {
+                Some(last) => last,
+                None => return ty.clone(),
+            };
+            if let syn::PathArguments::AngleBracketed(args) = &mut last.arguments {
+                args.args = args
+                    .args
+                    .iter()
+                    .filter_map(|arg| match arg {
+                        syn::GenericArgument::Lifetime(_) => None,
+                        syn::GenericArgument::Type(t) => Some(syn::GenericArgument::Type(unwrap_type(t))),
+                        arg => Some(arg.clone()),
+                    })
+                    .collect();
+                let types: Vec<&syn::Type> = args
+                    .args
+                    .iter()
+                    .filter_map(|arg| match arg {
+                        syn::GenericArgument::Type(t) => Some(t),
+                        _ => None,
+                    })
+                    .collect();
+                if let (true, [inner]) = (WRAPPERS.contains(&last.ident.to_string().as_str()), types.as_slice()) {
+                    return (*inner).clone();
+                }
+                if args.args.is_empty() {
+                    last.arguments = syn::PathArguments::None;
+                }
+            }
+            syn::Type::Path(p)
+        }
+        _ => ty.clone(),
+    }
+}
+
 fn type_as_string(ty: &syn::Type) -> String {
     use quote::ToTokens;
 
@@ -432,58 +1541,120 @@ fn type_as_string(ty: &syn::Type) -> String {
     tokens.to_string()
 }
 
-fn get_ident(ident: Option<&proc_macro2::Ident>, attrs: &[syn::Attribute], rename_all: &Option<String>) -> Id {
+/// Builds the identifier for a field or variant. An explicit `rename` wins over `rename_all`, and
+/// each direction is resolved separately; `apply` is the rule conversion for the item's position.
+fn get_ident(ident: Option<&proc_macro2::Ident>, attrs: &[syn::Attribute], rename_all: &RenameAll, apply: fn(RenameRule, &str) -> String) -> Id {
     let original = ident.map_or("???".to_string(), |id| id.to_string().replace("r#", ""));
-    let mut renamed = match rename_all {
-        None => original.clone(),
-        Some(value) => match value.as_str() {
-            "lowercase" => original.to_lowercase(),
-            "UPPERCASE" => original.to_uppercase(),
-            "PascalCase" => original.to_pascal_case(),
-            "camelCase" => original.to_camel_case(),
-            "snake_case" => original.to_snake_case(),
-            "SCREAMING_SNAKE_CASE" => original.to_screaming_snake_case(),
-            "kebab-case" => original.to_kebab_case(),
-            "SCREAMING-KEBAB-CASE" => original.to_kebab_case(),
-            _ => original.clone(),
-        },
-    };
+    let rename = |rule: Option<RenameRule>| rule.map_or_else(|| original.clone(), |rule| apply(rule, &original));
+
+    let (serialize, deserialize) = serde_directional_value(attrs, "rename");
+    let renamed = serialize.unwrap_or_else(|| rename(rename_all.serialize));
+    let renamed_deserialize = deserialize.unwrap_or_else(|| rename(rename_all.deserialize));
 
-    if let Some(s) = serde_rename(attrs) {
-        renamed = s;
+    Id {
+        original,
+        renamed,
+        renamed_deserialize,
+        aliases: serde_aliases(attrs),
     }
+}
 
-    Id { original, renamed }
+fn serde_aliases(attrs: &[syn::Attribute]) -> Vec<String> {
+    const PREFIX: &str = r##"alias = ""##;
+    const SUFFIX: &str = r##"""##;
+    attr_values(attrs, PREFIX, SUFFIX)
 }
 
-fn serde_rename(attrs: &[syn::Attribute]) -> Option<String> {
-    const PREFIX: &str = r##"rename = ""##;
+/// Reads `name = "value"` or `name(serialize = "a", deserialize = "b")` from `#[serde(...)]`
+/// attributes, returning the serialize and deserialize values.
+fn serde_directional_value(attrs: &[syn::Attribute], name: &str) -> (Option<String>, Option<String>) {
     const SUFFIX: &str = r##"""##;
-    attr_value(attrs, PREFIX, SUFFIX)
+    let both = format!(r##"{} = ""##, name);
+    let nested = format!("{} ", name);
+
+    for a in attrs.iter().filter(|a| a.path.is_ident("serde")) {
+        let attr_as_string = a.tts.to_string();
+        for v in parse_attr(&attr_as_string).unwrap_or_default() {
+            if v.starts_with(&both) && v.ends_with(SUFFIX) {
+                let value = v[both.len()..v.len() - SUFFIX.len()].to_string();
+                return (Some(value.clone()), Some(value));
+            }
+            if v.starts_with(&nested) {
+                let directions = parse_attr(&v[nested.len()..]).unwrap_or_default();
+                let value = |prefix: &'static str| {
+                    directions
+                        .iter()
+                        .find(|d| d.starts_with(prefix) && d.ends_with(SUFFIX))
+                        .map(|d| remove_prefix_suffix(d, prefix, SUFFIX).to_string())
+                };
+                return (value(r##"serialize = ""##), value(r##"deserialize = ""##));
+            }
+        }
+    }
+
+    (None, None)
 }
 
-fn serde_rename_all(attrs: &[syn::Attribute]) -> Option<String> {
-    const PREFIX: &str = r##"rename_all = ""##;
+/// Reads container-level `into = "..."` and `from = "..."` or `try_from = "..."`: the types serde
+/// converts to when serializing, and from when deserializing.
+fn serde_proxy_types(attrs: &[syn::Attribute]) -> (Option<String>, Option<String>) {
     const SUFFIX: &str = r##"""##;
-    attr_value(attrs, PREFIX, SUFFIX)
+    let into = attr_values(attrs, r##"into = ""##, SUFFIX).into_iter().next();
+    let from = attr_values(attrs, r##"from = ""##, SUFFIX)
+        .into_iter()
+        .chain(attr_values(attrs, r##"try_from = ""##, SUFFIX))
+        .next();
+    (into, from)
+}
+
+/// Returns true if a `#[serde(...)]` attribute contains `flag`, either as a bare word (`default`)
+/// or with a value (`default = "path"`).
+fn serde_flag(attrs: &[syn::Attribute], flag: &str) -> bool {
+    let with_value = format!("{} = ", flag);
+    attrs
+        .iter()
+        .filter(|a| a.path.is_ident("serde"))
+        .filter_map(|a| parse_attr(&a.tts.to_string()).map(|values| values.iter().any(|v| *v == flag || v.starts_with(&with_value))))
+        .any(|found| found)
+}
+
+/// Returns the `#[repr(...)]` integer type if the enum derives `serde_repr`'s `Serialize_repr` or
+/// `Deserialize_repr`. serde_repr itself requires the `repr`.
+fn serde_repr_type(attrs: &[syn::Attribute]) -> Option<String> {
+    const INTEGER_TYPES: &[&str] = &["i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize"];
+    let derives_repr = attrs
+        .iter()
+        .filter(|a| a.path.is_ident("derive"))
+        .any(|a| a.tts.to_string().contains("Serialize_repr") || a.tts.to_string().contains("Deserialize_repr"));
+    if !derives_repr {
+        return None;
+    }
+
+    attrs
+        .iter()
+        .filter(|a| a.path.is_ident("repr"))
+        .filter_map(|a| {
+            let attr_as_string = a.tts.to_string();
+            let values = parse_attr(&attr_as_string)?;
+            values.into_iter().find(|v| INTEGER_TYPES.contains(v)).map(str::to_string)
+        })
+        .next()
 }
 
 fn has_typeshare_marker(attrs: &[syn::Attribute]) -> bool {
+    attrs.iter().any(is_typeshare_attr)
+}
+
+/// `#[typeshare]`, also when written with its crate as `#[typeshare_annotation::typeshare]`.
+fn is_typeshare_attr(a: &syn::Attribute) -> bool {
     const TYPESHARE_MARKER: &str = "typeshare";
     let typeshare_ident = Ident::new(TYPESHARE_MARKER, Span::call_site());
-    for a in attrs {
-        if let Some(segment) = a.path.segments.iter().next() {
-            if segment.ident == typeshare_ident {
-                return true;
-            }
-        }
-    }
-
-    false
+    let mut segments = a.path.segments.iter();
+    segments.next().is_some_and(|segment| segment.ident == typeshare_ident) || segments.last().is_some_and(|segment| segment.ident == typeshare_ident)
 }
 
 /*
-    Process attributes and return value of the matching attribute, if found.
+    Process attributes and return the values of every matching attribute, e.g. for repeated `alias = "..."`.
     ```
     [
     Attribute
@@ -529,7 +1700,8 @@ fn has_typeshare_marker(attrs: &[syn::Attribute]) -> bool {
     ]
     ```
 */
-fn attr_value(attrs: &[syn::Attribute], prefix: &'static str, suffix: &'static str) -> Option<String> {
+fn attr_values(attrs: &[syn::Attribute], prefix: &'static str, suffix: &'static str) -> Vec<String> {
+    let mut found = Vec::new();
     for a in attrs {
         if let Some(segment) = a.path.segments.iter().next() {
             if segment.ident != Ident::new("serde", Span::call_site()) {
@@ -537,33 +1709,182 @@ fn attr_value(attrs: &[syn::Attribute], prefix: &'static str, suffix: &'static s
             }
 
             let attr_as_string = a.tts.to_string();
-            let values = parse_attr(&attr_as_string)?;
+            let values = match parse_attr(&attr_as_string) {
+                Some(values) => values,
+                None => continue,
+            };
 
             for v in values {
                 if v.starts_with(prefix) && v.ends_with(suffix) {
-                    return Some(remove_prefix_suffix(&v, prefix, suffix).to_string());
+                    found.push(remove_prefix_suffix(v, prefix, suffix).to_string());
                 }
             }
         }
     }
 
-    None
+    found
 }
 
-fn parse_attr<'x>(attr: &'x str) -> Option<Vec<&'x str>> {
+/// Splits `( a , b ( c , d ) , e = "f, g" )` into `a`, `b ( c , d )` and `e = "f, g"`.
+/// Commas inside nested groups and string literals don't split.
+fn parse_attr(attr: &str) -> Option<Vec<&str>> {
     const ATTR_PREFIX: &str = "( ";
     const ATTR_SUFFIX: &str = " )";
 
-    if attr.starts_with(ATTR_PREFIX) && attr.ends_with(ATTR_SUFFIX) {
-        let attr = remove_prefix_suffix(attr, ATTR_PREFIX, ATTR_SUFFIX);
-        return Some(attr.split(" , ").collect());
+    if !(attr.starts_with(ATTR_PREFIX) && attr.ends_with(ATTR_SUFFIX)) {
+        return None;
+    }
+    let attr = remove_prefix_suffix(attr, ATTR_PREFIX, ATTR_SUFFIX);
+
+    let mut values = Vec::new();
+    let (mut depth, mut in_string, mut escaped, mut start) = (0, false, false, 0);
+    for (i, c) in attr.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '(' | '[' | '{' if !in_string => depth += 1,
+            ')' | ']' | '}' if !in_string => depth -= 1,
+            ',' if !in_string && depth == 0 => {
+                values.push(attr[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
     }
+    values.push(attr[start..].trim());
 
-    None
+    Some(values)
+}
+
+/// Whether `path` names a std type, either by its full path or as a bare, imported name.
+fn is_std_path(path: &str) -> bool {
+    !path.contains("::") || ["std::", "core::", "alloc::"].iter().any(|prefix| path.starts_with(prefix))
+}
+
+/// The type a std type is serialized like, for those serde doesn't write as their own name:
+/// paths and addresses are strings, `NonZero` integers are plain integers, and `Duration` and
+/// `SystemTime` are objects described by the structs in `STD_STRUCTS`.
+fn std_wire_type(path: &str) -> Option<&'static str> {
+    if !is_std_path(path) {
+        return None;
+    }
+    let wire_type = match path.rsplit("::").next()? {
+        "PathBuf" | "Path" | "IpAddr" | "Ipv4Addr" | "Ipv6Addr" | "SocketAddr" | "SocketAddrV4" | "SocketAddrV6" => "String",
+        "NonZeroU8" => "u8",
+        "NonZeroU16" => "u16",
+        "NonZeroU32" => "u32",
+        "NonZeroU64" => "u64",
+        "NonZeroU128" => "u128",
+        "NonZeroUsize" => "usize",
+        "NonZeroI8" => "i8",
+        "NonZeroI16" => "i16",
+        "NonZeroI32" => "i32",
+        "NonZeroI64" => "i64",
+        "NonZeroI128" => "i128",
+        "NonZeroIsize" => "isize",
+        "Duration" => "RustDuration",
+        "SystemTime" => "RustSystemTime",
+        _ => return None,
+    };
+    Some(wire_type)
+}
+
+/// Whether `path` names an item of `krate`, either by its full path or as a bare, imported name.
+fn is_crate_path(path: &str, krate: &str) -> bool {
+    !path.contains("::") || path.split("::").next() == Some(krate)
+}
+
+/// The type a type from a popular crate is serialized like, for crates whose feature is enabled.
+/// Types backends map to something idiomatic get a canonical path, such as `uuid::Uuid` for a bare
+/// `Uuid`; the rest become the Rust type with the same JSON form.
+fn crate_wire_type(path: &str) -> Option<&'static str> {
+    let name = path.rsplit("::").next()?;
+    match name {
+        #[cfg(feature = "chrono")]
+        "DateTime" if is_crate_path(path, "chrono") => Some("chrono::DateTime"),
+        #[cfg(feature = "chrono")]
+        "NaiveDateTime" if is_crate_path(path, "chrono") => Some("chrono::NaiveDateTime"),
+        #[cfg(feature = "chrono")]
+        "NaiveDate" if is_crate_path(path, "chrono") => Some("chrono::NaiveDate"),
+        #[cfg(feature = "chrono")]
+        "NaiveTime" if is_crate_path(path, "chrono") => Some("chrono::NaiveTime"),
+        // Written in time's own human-readable format, which isn't ISO 8601.
+        #[cfg(feature = "time")]
+        "OffsetDateTime" | "PrimitiveDateTime" if is_crate_path(path, "time") => Some("String"),
+        #[cfg(feature = "time")]
+        "Date" | "Time" if path.starts_with("time::") => Some("String"),
+        #[cfg(feature = "uuid")]
+        "Uuid" if is_crate_path(path, "uuid") => Some("uuid::Uuid"),
+        #[cfg(feature = "url")]
+        "Url" if is_crate_path(path, "url") => Some("url::Url"),
+        #[cfg(feature = "serde_json")]
+        "Value" if is_crate_path(path, "serde_json") => Some("serde_json::Value"),
+        // Written as a string by default, to keep its precision.
+        #[cfg(feature = "rust_decimal")]
+        "Decimal" if is_crate_path(path, "rust_decimal") => Some("String"),
+        _ => None,
+    }
+}
+
+/// Structs for the std types serde writes as objects, added to the output when a type refers to them.
+const STD_STRUCTS: &[(&str, &str)] = &[
+    (
+        "RustDuration",
+        "/// A `std::time::Duration`: whole seconds, and the nanoseconds past them.
+        #[typeshare]
+        pub struct RustDuration {
+            pub secs: u64,
+            pub nanos: u32,
+        }",
+    ),
+    (
+        "RustSystemTime",
+        "/// A `std::time::SystemTime`: whole seconds since the Unix epoch, and the nanoseconds past them.
+        #[typeshare]
+        pub struct RustSystemTime {
+            pub secs_since_epoch: u64,
+            pub nanos_since_epoch: u32,
+        }",
+    ),
+];
+
+/// The tokens between the outermost angle brackets: `T` for `VecDeque < T >`, `K , V` for `BTreeMap < K , V >`.
+fn generic_args(ty: &str) -> &str {
+    match (ty.find('<'), ty.rfind('>')) {
+        (Some(start), Some(end)) if start < end => ty[start + 1..end].trim(),
+        _ => ty,
+    }
+}
+
+/// Whether `s` is a plain path such as `std :: result :: Result`, rather than a reference, tuple or array.
+fn is_type_path(s: &str) -> bool {
+    !s.trim().is_empty() && s.chars().all(|c| c.is_alphanumeric() || c == '_' || c == ':' || c == ' ')
+}
+
+/// Splits generic arguments at their top-level commas.
+fn split_type_args(args: &str) -> Vec<&str> {
+    let mut values = Vec::new();
+    let (mut depth, mut start) = (0, 0);
+    for (i, c) in args.char_indices() {
+        match c {
+            '<' | '(' | '[' => depth += 1,
+            '>' | ')' | ']' => depth -= 1,
+            ',' if depth == 0 => {
+                values.push(args[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    values.push(args[start..].trim());
+    values.retain(|v| !v.is_empty());
+    values
 }
 
 fn remove_prefix_suffix<'a>(src: &'a str, prefix: &'static str, suffix: &'static str) -> &'a str {
-    if src.starts_with(prefix) && src.ends_with(suffix) {
+    // The prefix and suffix may overlap, as in an empty `( )`.
+    if src.len() >= prefix.len() + suffix.len() && src.starts_with(prefix) && src.ends_with(suffix) {
         return &src[prefix.len()..src.len() - suffix.len()];
     }
     src
@@ -1,3 +1,6 @@
+pub mod case;
+pub mod config;
+pub mod java;
 pub mod language;
 pub mod swift;
 pub mod typescript;
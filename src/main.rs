@@ -1,10 +1,7 @@
 use clap::{App, Arg};
-use language::{Generator, Language};
-
-mod java;
-mod language;
-mod swift;
-mod typescript;
+use typeshare::config::Config;
+use typeshare::language::{self, Generator, Language};
+use typeshare::{java, swift, typescript};
 
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 
@@ -12,6 +9,14 @@ const ARG_TYPE: &str = "TYPE";
 const ARG_MARKER: &str = "MARKER";
 const ARG_SWIFT_PREFIX: &str = "SWIFTPREFIX";
 const ARG_JAVA_PACKAGE: &str = "JAVAPACKAGE";
+const ARG_SPLIT_DIRECTIONS: &str = "SPLITDIRECTIONS";
+const ARG_SWIFT_WRAP_TRANSPARENT: &str = "SWIFTWRAPTRANSPARENT";
+const ARG_TYPESCRIPT_CHECK_UNKNOWN_FIELDS: &str = "TYPESCRIPTCHECKUNKNOWNFIELDS";
+const ARG_TYPESCRIPT_UINT8ARRAY: &str = "TYPESCRIPTUINT8ARRAY";
+const ARG_TYPESCRIPT_SET: &str = "TYPESCRIPTSET";
+const ARG_TYPESCRIPT_LARGE_INTEGERS: &str = "TYPESCRIPTLARGEINTEGERS";
+const ARG_JAVA_LARGE_INTEGERS: &str = "JAVALARGEINTEGERS";
+const ARG_CONFIG: &str = "CONFIG";
 
 fn main() {
     let options = App::new("typeshare")
@@ -48,6 +53,65 @@ fn main() {
                 .takes_value(true)
                 .required(false),
         )
+        .arg(
+            Arg::with_name(ARG_SPLIT_DIRECTIONS)
+                .long("split-directions")
+                .help("Emit separate Request/Response types for structs with skipped or defaulted fields")
+                .takes_value(false)
+                .required(false),
+        )
+        .arg(
+            Arg::with_name(ARG_SWIFT_WRAP_TRANSPARENT)
+                .long("swift-wrap-transparent")
+                .help("Emit #[serde(transparent)] structs as Swift wrapper types instead of type aliases")
+                .takes_value(false)
+                .required(false),
+        )
+        .arg(
+            Arg::with_name(ARG_TYPESCRIPT_CHECK_UNKNOWN_FIELDS)
+                .long("typescript-check-unknown-fields")
+                .help("Emit TypeScript functions that reject unknown fields for #[serde(deny_unknown_fields)] structs")
+                .takes_value(false)
+                .required(false),
+        )
+        .arg(
+            Arg::with_name(ARG_TYPESCRIPT_UINT8ARRAY)
+                .long("typescript-uint8array")
+                .help("Type byte buffers as Uint8Array in TypeScript, with helpers to convert from JSON")
+                .takes_value(false)
+                .required(false),
+        )
+        .arg(
+            Arg::with_name(ARG_TYPESCRIPT_SET)
+                .long("typescript-set")
+                .help("Type sets as Set in TypeScript instead of arrays")
+                .takes_value(false)
+                .required(false),
+        )
+        .arg(
+            Arg::with_name(ARG_TYPESCRIPT_LARGE_INTEGERS)
+                .long("typescript-large-integers")
+                .help("Type 64- and 128-bit integers in TypeScript as a number, a bigint, or a string sent by Rust")
+                .possible_values(&["number", "bigint", "string"])
+                .takes_value(true)
+                .required(false),
+        )
+        .arg(
+            Arg::with_name(ARG_JAVA_LARGE_INTEGERS)
+                .long("java-large-integers")
+                .help("Type u64 and usize in Java as a long or a BigInteger")
+                .possible_values(&["long", "biginteger"])
+                .takes_value(true)
+                .required(false),
+        )
+        .arg(
+            Arg::with_name(ARG_CONFIG)
+                .short("c")
+                .long("config")
+                .help("TOML file mapping Rust types to types in each language")
+                .takes_value(true)
+                .required(false),
+        )
         .arg(Arg::with_name("input.rs").help("Sets the input file to use").required(true).index(1))
         .get_matches();
 
@@ -61,14 +125,37 @@ fn main() {
         _ => Box::new(typescript::TypeScript {}),
     };
 
+    let config = match options.value_of(ARG_CONFIG) {
+        Some(path) => Config::from_file(path).expect("failed to read config"),
+        None => Config::default(),
+    };
+
     let params = language::Params {
         use_marker: options.is_present(ARG_MARKER),
         swift_prefix: options.value_of(ARG_SWIFT_PREFIX).unwrap_or("").to_string(),
         java_package: options.value_of(ARG_JAVA_PACKAGE).unwrap_or("").to_string(),
+        split_directions: options.is_present(ARG_SPLIT_DIRECTIONS),
+        swift_wrap_transparent: options.is_present(ARG_SWIFT_WRAP_TRANSPARENT),
+        typescript_check_unknown_fields: options.is_present(ARG_TYPESCRIPT_CHECK_UNKNOWN_FIELDS),
+        typescript_uint8array: options.is_present(ARG_TYPESCRIPT_UINT8ARRAY),
+        typescript_set: options.is_present(ARG_TYPESCRIPT_SET),
+        typescript_large_integers: options
+            .value_of(ARG_TYPESCRIPT_LARGE_INTEGERS)
+            .and_then(language::TypeScriptLargeIntegers::from_name)
+            .unwrap_or_default(),
+        java_large_integers: options
+            .value_of(ARG_JAVA_LARGE_INTEGERS)
+            .and_then(language::JavaLargeIntegers::from_name)
+            .unwrap_or_default(),
+        config,
+        ..Default::default()
     };
 
     let mut generator = Generator::new(lang.as_mut(), params);
 
     let mut out = std::io::stdout();
     generator.process_file(filename, &mut out).expect("failed to process");
+    for warning in generator.warnings() {
+        eprintln!("warning: {}", warning);
+    }
 }
@@ -1,17 +1,96 @@
 use std::io::Write;
 
-use crate::language::{Language, Params, RustAlgebraicEnum, RustConstEnum, RustStruct};
+use crate::config::TypeMapping;
+use crate::language::{map_key, BytesEncoding, Direction, EnumTagging, Language, MapKey, Params, RustAlgebraicEnum, RustConst, RustConstEnum, RustField, RustStruct, RustType};
 
 #[derive(Default)]
-pub struct Swift {}
+pub struct Swift {
+    /// Set once a struct needs `AnyCodingKey` for keys outside its `CodingKeys`.
+    uses_any_coding_key: bool,
+    /// Set once a struct needs `DoubleOption` for an `Option<Option<T>>` field.
+    uses_double_option: bool,
+    /// Set once a struct decodes a field that has `#[serde(alias = "...")]` names.
+    uses_aliases: bool,
+    /// Set once a type refers to `RustResult` for a `Result<T, E>`.
+    uses_result: bool,
+    /// Lengths of the tuples types refer to, each needing a `TupleN` struct, or `Unit` for `()`.
+    tuple_arities: Vec<usize>,
+    /// Set once a type refers to `Date`, which needs the RFC 3339 decoding strategy.
+    uses_dates: bool,
+    /// Set once a type refers to `AnyCodable` for a `serde_json::Value`.
+    uses_any_codable: bool,
+    /// Key types of the maps written with `StringKeyed`, each needing a `StringKey` conformance.
+    string_keys: Vec<String>,
+}
 
 impl Swift {
     pub fn new() -> Self {
-        Swift {}
+        Swift {
+            uses_any_coding_key: false,
+            uses_double_option: false,
+            uses_aliases: false,
+            uses_result: false,
+            tuple_arities: Vec::new(),
+            uses_dates: false,
+            uses_any_codable: false,
+            string_keys: Vec::new(),
+        }
+    }
+
+    /// Records the helper types that `ty` refers to, for `end_file` to write.
+    fn note_helper_types(&mut self, ty: &RustType, params: &Params) {
+        ty.walk(&mut |ty| match ty {
+            RustType::Map(key, _) => match map_key(key, params) {
+                MapKey::Integer(int) => self.string_keys.push(swift_primitive(&int).to_string()),
+                MapKey::Enum(name) => self.string_keys.push(format!("{}{}", params.swift_prefix, name)),
+                _ => {}
+            },
+            RustType::Result(..) => self.uses_result = true,
+            RustType::Tuple(elems) => self.tuple_arities.push(elems.len()),
+            RustType::Other(path, _) if swift_primitive(path) == "Date" => self.uses_dates = true,
+            RustType::Other(path, _) if path == "serde_json::Value" => self.uses_any_codable = true,
+            _ => {}
+        });
     }
 }
 
-fn swift_type(s: &str) -> &str {
+fn swift_type(s: &str, params: &Params) -> String {
+    swift_type_of(&RustType::parse(s).resolve(params), params)
+}
+
+fn swift_type_of(ty: &RustType, params: &Params) -> String {
+    if let RustType::Other(path, _) = ty {
+        if let Some(mapped) = params.config.type_mapping(path).and_then(|m| m.swift.as_ref()) {
+            return mapped.clone();
+        }
+    }
+    match ty {
+        RustType::Vec(inner) | RustType::Array(inner, _) => format!("[{}]", swift_type_of(inner, params)),
+        RustType::Set(inner) => format!("Set<{}>", swift_type_of(inner, params)),
+        RustType::Map(key, value) => match map_key(key, params) {
+            // Swift writes dictionaries keyed by anything but `String` and `Int` as arrays.
+            MapKey::Integer(_) | MapKey::Enum(_) => {
+                format!("{}StringKeyed<{}, {}>", params.swift_prefix, swift_type_of(key, params), swift_type_of(value, params))
+            }
+            _ => format!("[{}: {}]", swift_type_of(key, params), swift_type_of(value, params)),
+        },
+        RustType::Option(inner) => format!("{}?", swift_type_of(inner, params)),
+        RustType::Result(ok, err) => format!("{}RustResult<{}, {}>", params.swift_prefix, swift_type_of(ok, params), swift_type_of(err, params)),
+        RustType::Tuple(elems) if elems.is_empty() => format!("{}Unit", params.swift_prefix),
+        RustType::Tuple(elems) => {
+            let elems: Vec<String> = elems.iter().map(|elem| swift_type_of(elem, params)).collect();
+            format!("{}Tuple{}<{}>", params.swift_prefix, elems.len(), elems.join(", "))
+        }
+        RustType::Other(path, _) if path == "serde_json::Value" => format!("{}AnyCodable", params.swift_prefix),
+        RustType::Other(path, args) if args.is_empty() => swift_primitive(path).to_string(),
+        RustType::Other(path, args) => {
+            let args: Vec<String> = args.iter().map(|arg| swift_type_of(arg, params)).collect();
+            format!("{}<{}>", path, args.join(", "))
+        }
+    }
+}
+
+fn swift_primitive(s: &str) -> &str {
     match s {
         "String" => "String",
         "i8" => "Int8",
@@ -27,6 +106,10 @@ fn swift_type(s: &str) -> &str {
         "bool" => "Bool",
         "f32" => "Float",
         "f64" => "Double",
+        "chrono::DateTime" => "Date",
+        "chrono::NaiveDateTime" | "chrono::NaiveDate" | "chrono::NaiveTime" => "String",
+        "uuid::Uuid" => "UUID",
+        "url::Url" => "URL",
         _ => s,
     }
 }
@@ -46,13 +129,21 @@ fn swift_lit_type(lit: &Option<syn::Lit>) -> &'static str {
 }
 
 impl Language for Swift {
-    fn begin_file(&mut self, w: &mut dyn Write, _params: &Params) -> std::io::Result<()> {
+    fn mapped_type<'a>(&self, mapping: &'a TypeMapping) -> Option<&'a str> {
+        mapping.swift.as_deref()
+    }
+
+    fn begin_file(&mut self, w: &mut dyn Write, params: &Params) -> std::io::Result<()> {
         writeln!(w, "/*")?;
         writeln!(w, " Generated by typeshare {}", env!("CARGO_PKG_VERSION"))?;
         writeln!(w, "*/")?;
 
         writeln!(w)?;
-        writeln!(w, "import Foundation\n")?;
+        writeln!(w, "import Foundation")?;
+        for line in params.config.imports.swift.iter() {
+            writeln!(w, "{}", line)?;
+        }
+        writeln!(w)?;
         Ok(())
     }
 
@@ -61,26 +152,49 @@ impl Language for Swift {
         writeln!(w, "public struct {}{}: Codable {{", params.swift_prefix, rs.id.original)?;
 
         for f in rs.fields.iter() {
-            write_comments(w, 1, &f.comments)?;
-            if f.is_vec {
-                writeln!(w, "\tpublic let {}: [{}]{}", f.id.renamed, swift_type(&f.ty), option_symbol(f.is_optional))?;
-            } else {
-                writeln!(w, "\tpublic let {}: {}{}", f.id.renamed, swift_type(&f.ty), option_symbol(f.is_optional))?;
-            }
+            write_comments(w, 1, &f.comments_with_len())?;
+            writeln!(w, "\tpublic let {}: {}", f.id.identifier(), swift_field_type(f, params, rs.direction))?;
         }
 
-        let mut init_params: Vec<String> = Vec::new();
+        // Synthesized `Codable` can't merge containers, tell a missing key from `null`, or use
+        // different keys for decoding and encoding.
+        let needs_encoder = rs
+            .fields
+            .iter()
+            .any(|f| f.is_flattened || f.is_double_option || has_deserialize_name(f) || is_byte_array(f));
+        let rejects_unknown_keys = rs.deny_unknown_fields && rs.known_keys().is_some();
+        if needs_encoder || rejects_unknown_keys || rs.fields.iter().any(|f| f.has_default || !f.id.aliases.is_empty()) {
+            write_coding_keys(w, rs)?;
+            write_decoder(w, params, rs)?;
+        } else if rs.fields.iter().any(|f| f.id.identifier() != f.id.renamed) {
+            // Synthesized `Codable` still needs the keys of properties named other than their key.
+            write_coding_keys(w, rs)?;
+        }
+        if needs_encoder {
+            write_encoder(w, params, rs)?;
+        }
+        if rejects_unknown_keys || rs.fields.iter().any(|f| f.is_catch_all() || has_deserialize_name(f) || !f.id.aliases.is_empty()) {
+            self.uses_any_coding_key = true;
+        }
+        if rs.fields.iter().any(|f| !f.id.aliases.is_empty()) {
+            self.uses_aliases = true;
+        }
+        if rs.fields.iter().any(|f| f.is_double_option) {
+            self.uses_double_option = true;
+        }
         for f in rs.fields.iter() {
-            if f.is_vec {
-                init_params.push(format!("{}: [{}]{}", f.id.renamed, swift_type(&f.ty), option_symbol(f.is_optional)));
-            } else {
-                init_params.push(format!("{}: {}{}", f.id.renamed, swift_type(&f.ty), option_symbol(f.is_optional)));
-            }
+            self.note_helper_types(&f.rust_type(params), params);
         }
 
+        let init_params: Vec<String> = rs
+            .fields
+            .iter()
+            .map(|f| format!("{}: {}", f.id.identifier(), swift_field_type(f, params, rs.direction)))
+            .collect();
+
         writeln!(w, "\n\tpublic init({}) {{", init_params.join(", "))?;
         for f in rs.fields.iter() {
-            writeln!(w, "\t\tself.{} = {}", f.id.renamed, f.id.renamed)?;
+            writeln!(w, "\t\tself.{} = {}", f.id.identifier(), f.id.identifier())?;
         }
         writeln!(w, "\t}}")?;
         writeln!(w, "}}\n")?;
@@ -90,18 +204,101 @@ impl Language for Swift {
         Ok(())
     }
 
+    fn write_transparent_struct(&mut self, w: &mut dyn Write, params: &Params, rs: &RustStruct) -> std::io::Result<()> {
+        let f = &rs.fields[0];
+        // Read and written as the inner type, so bytes sent as an array of numbers are `[UInt8]`, not base64 `Data`.
+        let base = if is_byte_array(f) { "[UInt8]".to_string() } else { swift_base_type(f, params) };
+        let inner = format!("{}{}", base, option_symbol(f.is_optional));
+        self.note_helper_types(&f.rust_type(params), params);
+        write_comments(w, 0, &rs.comments)?;
+        if !params.swift_wrap_transparent {
+            writeln!(w, "public typealias {}{} = {}\n", params.swift_prefix, rs.id.original, inner)?;
+            return Ok(());
+        }
+
+        // A distinct type that still reads and writes the bare inner value.
+        writeln!(
+            w,
+            "public struct {prefix}{name}: Codable {{
+	public let value: {inner}
+
+	public init(_ value: {inner}) {{
+		self.value = value
+	}}
+
+	public init(from decoder: Decoder) throws {{
+		let container = try decoder.singleValueContainer()
+		self.value = try container.decode({inner}.self)
+	}}
+
+	public func encode(to encoder: Encoder) throws {{
+		var container = encoder.singleValueContainer()
+		try container.encode(value)
+	}}
+}}
+",
+            prefix = params.swift_prefix,
+            name = rs.id.original,
+            inner = inner
+        )?;
+        Ok(())
+    }
+
+    fn end_file(&mut self, w: &mut dyn Write, params: &Params) -> std::io::Result<()> {
+        if self.uses_any_coding_key {
+            write_any_coding_key(w, params)?;
+        }
+        if self.uses_aliases {
+            write_alias_decoding(w, params)?;
+        }
+        if self.uses_double_option {
+            write_double_option(w, params)?;
+        }
+        if self.uses_result {
+            write_result(w, params)?;
+        }
+        self.tuple_arities.sort_unstable();
+        self.tuple_arities.dedup();
+        for arity in self.tuple_arities.iter() {
+            match arity {
+                0 => write_unit(w, params)?,
+                _ => write_tuple(w, params, *arity)?,
+            }
+        }
+        if self.uses_any_codable {
+            write_any_codable(w, params)?;
+        }
+        if self.uses_dates {
+            write_date_strategy(w)?;
+        }
+        if !self.string_keys.is_empty() {
+            let mut keys = Vec::new();
+            for key in self.string_keys.iter() {
+                if !keys.contains(key) {
+                    keys.push(key.clone());
+                }
+            }
+            write_string_keyed(w, params, &keys)?;
+        }
+        Ok(())
+    }
+
     fn write_const_enum(&mut self, w: &mut dyn Write, params: &Params, e: &RustConstEnum) -> std::io::Result<()> {
+        if let Some(repr @ ("i128" | "u128")) = e.repr.as_deref() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("{} is `#[repr({})]`, and Swift enums can't have 128-bit raw values", e.id.original, repr),
+            ));
+        }
         write_comments(w, 0, &e.comments)?;
-        writeln!(w, "public enum {}{}: {}, Codable {{", params.swift_prefix, e.id.original, swift_lit_type(&e.ty))?;
+        if e.accepts_unknown {
+            return write_open_enum(w, params, e);
+        }
+        writeln!(w, "public enum {}{}: {}, Codable {{", params.swift_prefix, e.id.original, swift_raw_type(e))?;
 
         for c in e.cases.iter() {
             write_comments(w, 1, &c.comments)?;
-            let mut printed_value = lit_value(&c.value).to_string();
-            if printed_value == "" {
-                printed_value = format!(r##""{}""##, &c.id.renamed);
-            }
-
-            writeln!(w, "\tcase {} = {}", c.id.renamed, &printed_value)?;
+            writeln!(w, "\tcase {} = {}", c.id.identifier(), swift_raw_value(c))?;
         }
 
         writeln!(w, "}}\n")?;
@@ -113,51 +310,108 @@ impl Language for Swift {
         let enum_type_name = format!("{}{}", params.swift_prefix, e.id.original);
         writeln!(w, "public enum {}: Codable {{", enum_type_name)?;
 
+        let key = |name: &str| format!("{}AnyCodingKey(stringValue: \"{}\")!", params.swift_prefix, name);
         let mut decoding_cases: Vec<String> = Vec::new();
         let mut encoding_cases: Vec<String> = Vec::new();
 
         for c in e.cases.iter() {
             write_comments(w, 1, &c.comments)?;
-            let case_type = if c.value.is_vec {
-                format!("[{}{}]", swift_type(&c.value.ty), option_symbol(c.value.is_optional))
-            } else {
-                format!("{}{}", swift_type(&c.value.ty), option_symbol(c.value.is_optional))
-            };
+            let case_type = format!("{}{}", swift_type_of(&c.value.rust_type(params), params), option_symbol(c.value.is_optional));
+            self.note_helper_types(&c.value.rust_type(params), params);
 
-            writeln!(w, "\tcase {}({})", c.id.renamed, case_type)?;
+            writeln!(w, "\tcase {}({})", c.id.identifier(), case_type)?;
 
-            decoding_cases.push(format!(
-                "
-		if let x = try? container.decode({}.self) {{
-			self = .{}(x)
-			return
-		}}",
-                case_type, c.id.renamed,
-            ));
-
-            encoding_cases.push(format!(
-                "
-		case .{}(let x):
-			try container.encode(x)",
-                c.id.renamed,
-            ));
+            let case = c.id.identifier();
+            let (decoding, encoding) = match &e.tagging {
+                EnumTagging::Untagged => (
+                    format!(
+                        "\n\t\tif let x = try? container.decode({}.self) {{\n\t\t\tself = .{}(x)\n\t\t\treturn\n\t\t}}",
+                        case_type, case
+                    ),
+                    "\n\t\t\ttry container.encode(x)".to_string(),
+                ),
+                EnumTagging::External => (
+                    format!(
+                        "\n\t\tif container.contains({key}) {{\n\t\t\tself = .{}(try container.decode({}.self, forKey: {key}))\n\t\t\treturn\n\t\t}}",
+                        case,
+                        case_type,
+                        key = key(&c.id.renamed_deserialize)
+                    ),
+                    format!("\n\t\t\ttry container.encode(x, forKey: {})", key(&c.id.renamed)),
+                ),
+                // The variant's own fields sit beside the tag, so it decodes from the whole object.
+                EnumTagging::Internal { tag } => (
+                    format!(
+                        "\n\t\tcase \"{}\":\n\t\t\tself = .{}(try {}(from: decoder))\n\t\t\treturn",
+                        c.id.renamed_deserialize, case, case_type
+                    ),
+                    format!("\n\t\t\ttry container.encode(\"{}\", forKey: {})\n\t\t\ttry x.encode(to: encoder)", c.id.renamed, key(tag)),
+                ),
+                EnumTagging::Adjacent { tag, content } => (
+                    format!(
+                        "\n\t\tcase \"{}\":\n\t\t\tself = .{}(try container.decode({}.self, forKey: {}))\n\t\t\treturn",
+                        c.id.renamed_deserialize,
+                        case,
+                        case_type,
+                        key(content)
+                    ),
+                    format!(
+                        "\n\t\t\ttry container.encode(\"{}\", forKey: {})\n\t\t\ttry container.encode(x, forKey: {})",
+                        c.id.renamed,
+                        key(tag),
+                        key(content)
+                    ),
+                ),
+            };
+            decoding_cases.push(decoding);
+            encoding_cases.push(format!("\n\t\tcase .{}(let x):{}", case, encoding));
         }
 
+        let (decoding_container, encoding_container, decoding_switch) = match &e.tagging {
+            EnumTagging::Untagged => (
+                "decoder.singleValueContainer()".to_string(),
+                "encoder.singleValueContainer()".to_string(),
+                decoding_cases.join(""),
+            ),
+            EnumTagging::External => {
+                self.uses_any_coding_key = true;
+                (
+                    format!("decoder.container(keyedBy: {}AnyCodingKey.self)", params.swift_prefix),
+                    format!("encoder.container(keyedBy: {}AnyCodingKey.self)", params.swift_prefix),
+                    decoding_cases.join(""),
+                )
+            }
+            EnumTagging::Internal { tag } | EnumTagging::Adjacent { tag, .. } => {
+                self.uses_any_coding_key = true;
+                (
+                    format!("decoder.container(keyedBy: {}AnyCodingKey.self)", params.swift_prefix),
+                    format!("encoder.container(keyedBy: {}AnyCodingKey.self)", params.swift_prefix),
+                    format!(
+                        "\n\t\tswitch try container.decode(String.self, forKey: {}) {{{}\n\t\tdefault:\n\t\t\tbreak\n\t\t}}",
+                        key(tag),
+                        decoding_cases.join("")
+                    ),
+                )
+            }
+        };
+
         writeln!(
             w,
             r#"
 	public init(from decoder: Decoder) throws {{
-		let container = try decoder.singleValueContainer(){decoding_switch}
+		let container = try {decoding_container}{decoding_switch}
 		throw DecodingError.typeMismatch({type_name}.self, DecodingError.Context(codingPath: decoder.codingPath, debugDescription: "Wrong type for {type_name}"))
 	}}
 
 	public func encode(to encoder: Encoder) throws {{
-		var container = encoder.singleValueContainer()
+		var container = {encoding_container}
 		switch self {{{encoding_switch}
 		}}
 	}}"#,
             type_name = enum_type_name,
-            decoding_switch = decoding_cases.join(""),
+            decoding_container = decoding_container,
+            encoding_container = encoding_container,
+            decoding_switch = decoding_switch,
             encoding_switch = encoding_cases.join(""),
         )?;
 
@@ -182,30 +436,773 @@ fn option_symbol(optional: bool) -> &'static str {
     }
 }
 
+/// Swift type of a field, without the trailing `?` for optional fields.
+fn swift_base_type(f: &RustField, params: &Params) -> String {
+    if let Some(ty) = &f.type_override.swift {
+        ty.clone()
+    } else if f.bytes.is_some() {
+        "Data".to_string()
+    } else {
+        swift_type_of(&f.rust_type(params), params)
+    }
+}
+
+/// Value a missing `#[serde(default)]` field falls back to, if the type has an obvious one.
+fn swift_default_value(f: &RustField, params: &Params) -> Option<&'static str> {
+    if f.type_override.swift.is_some() {
+        return None;
+    }
+    if f.bytes.is_some() {
+        return Some("Data()");
+    }
+    if f.is_vec || matches!(f.rust_type(params), RustType::Set(_)) {
+        return Some("[]");
+    }
+    if f.is_hash_map {
+        return Some("[:]");
+    }
+
+    match swift_primitive(&f.ty) {
+        "String" => Some(r#""""#),
+        "Int8" | "Int16" | "Int32" | "Int64" | "UInt8" | "UInt16" | "UInt32" | "UInt64" | "Int" | "UInt" | "Float" | "Double" => Some("0"),
+        "Bool" => Some("false"),
+        _ => None,
+    }
+}
+
+/// Fields that may be `null` or missing are declared optional, except defaulted fields with a known
+/// fallback value and double options, which get their own wrapper.
+fn swift_is_optional(f: &RustField, params: &Params, direction: Direction) -> bool {
+    if f.is_double_option {
+        return false;
+    }
+    if f.is_nullable(direction) {
+        return true;
+    }
+    let has_fallback = f.has_default && swift_default_value(f, params).is_some();
+    (f.is_omittable(direction) || f.has_default) && !has_fallback
+}
+
+fn swift_field_type(f: &RustField, params: &Params, direction: Direction) -> String {
+    if f.is_double_option {
+        format!("{}DoubleOption<{}>", params.swift_prefix, swift_base_type(f, params))
+    } else {
+        format!("{}{}", swift_base_type(f, params), option_symbol(swift_is_optional(f, params, direction)))
+    }
+}
+
+/// `Data` is coded as base64 by default, so bytes sent as an array of numbers go through `[UInt8]`.
+fn is_byte_array(f: &RustField) -> bool {
+    f.bytes == Some(BytesEncoding::Array)
+}
+
+/// Returns true if Rust expects this field under a different name than it writes.
+fn has_deserialize_name(f: &RustField) -> bool {
+    f.id.renamed_deserialize != f.id.renamed
+}
+
+/// Keys named other than their property, such as `user-id`, get the name as their raw value.
+fn write_coding_keys(w: &mut dyn Write, rs: &RustStruct) -> std::io::Result<()> {
+    let keys: Vec<String> = rs
+        .fields
+        .iter()
+        .filter(|f| !f.is_flattened)
+        .map(|f| match f.id.identifier() {
+            identifier if identifier == f.id.renamed => identifier,
+            identifier => format!("{} = {:?}", identifier, f.id.renamed),
+        })
+        .collect();
+    writeln!(w, "\n\tenum CodingKeys: String, CodingKey {{")?;
+    if !keys.is_empty() {
+        writeln!(w, "\t\tcase {}", keys.join(", "))?;
+    }
+    writeln!(w, "\t}}")?;
+    Ok(())
+}
+
+/// Writes a decoder that tolerates missing `#[serde(default)]` fields and reads flattened fields
+/// from the same keyed container as their parent. Catch-all maps collect the keys that don't
+/// belong to a named field.
+fn write_decoder(w: &mut dyn Write, params: &Params, rs: &RustStruct) -> std::io::Result<()> {
+    writeln!(w, "\n\tpublic init(from decoder: Decoder) throws {{")?;
+    if let (true, Some(keys)) = (rs.deny_unknown_fields, rs.known_keys()) {
+        let keys: Vec<String> = keys.iter().map(|k| format!("{:?}", k)).collect();
+        writeln!(w, "\t\tlet knownKeys: Set<String> = [{}]", keys.join(", "))?;
+        writeln!(
+            w,
+            "\t\tif let unknownKey = try decoder.container(keyedBy: {}AnyCodingKey.self).allKeys.first(where: {{ !knownKeys.contains($0.stringValue) }}) {{",
+            params.swift_prefix
+        )?;
+        writeln!(
+            w,
+            "\t\t\tthrow DecodingError.dataCorrupted(DecodingError.Context(codingPath: decoder.codingPath + [unknownKey], debugDescription: \"Unknown field `\\(unknownKey.stringValue)`\"))"
+        )?;
+        writeln!(w, "\t\t}}")?;
+    }
+    if rs.fields.iter().any(|f| !f.is_flattened && f.id.aliases.is_empty()) {
+        writeln!(w, "\t\tlet container = try decoder.container(keyedBy: CodingKeys.self)")?;
+    }
+    if rs.fields.iter().any(|f| !f.id.aliases.is_empty()) {
+        writeln!(w, "\t\tlet aliasContainer = try decoder.container(keyedBy: {}AnyCodingKey.self)", params.swift_prefix)?;
+    }
+    for f in rs.fields.iter() {
+        if let (true, Some((_, value))) = (f.is_catch_all(), f.map_types()) {
+            writeln!(
+                w,
+                "\t\tlet {}Container = try decoder.container(keyedBy: {}AnyCodingKey.self)",
+                f.id.identifier(),
+                params.swift_prefix
+            )?;
+            writeln!(w, "\t\tvar {} = {}()", f.id.identifier(), swift_base_type(f, params))?;
+            // Keys read by flattened siblings belong to them, not to the catch-all.
+            let flattened_keys = flattened_keys(rs, params);
+            let unclaimed = if flattened_keys.is_empty() {
+                ""
+            } else {
+                writeln!(w, "\t\tlet flattenedKeys: Set<String> = [{}]", flattened_keys.join(", "))?;
+                " && !flattenedKeys.contains(key.stringValue)"
+            };
+            writeln!(
+                w,
+                "\t\tfor key in {}Container.allKeys where CodingKeys(stringValue: key.stringValue) == nil{} {{",
+                f.id.identifier(),
+                unclaimed
+            )?;
+            writeln!(
+                w,
+                "\t\t\t{0}[key.stringValue] = try {0}Container.decode({1}.self, forKey: key)",
+                f.id.identifier(),
+                swift_type(value, params)
+            )?;
+            writeln!(w, "\t\t}}")?;
+            writeln!(w, "\t\tself.{0} = {0}", f.id.identifier())?;
+            continue;
+        }
+        if f.is_flattened {
+            let attempt = if swift_is_optional(f, params, rs.direction) { "try?" } else { "try" };
+            writeln!(w, "\t\tself.{} = {} {}(from: decoder)", f.id.identifier(), attempt, swift_base_type(f, params))?;
+            continue;
+        }
+        if f.is_double_option {
+            // `decodeIfPresent` treats `null` like a missing key, so check for the key first.
+            let (ty, conversion) = if is_byte_array(f) {
+                (format!("{}DoubleOption<[UInt8]>", params.swift_prefix), ".map { Data($0) }")
+            } else {
+                (swift_field_type(f, params, rs.direction), "")
+            };
+            if f.id.aliases.is_empty() {
+                writeln!(
+                    w,
+                    "\t\tself.{name} = container.contains(.{name}) ? try container.decode({ty}.self, forKey: .{name}){conversion} : .absent",
+                    name = f.id.identifier(),
+                    ty = ty,
+                    conversion = conversion
+                )?;
+            } else {
+                let keys: Vec<String> = std::iter::once(&f.id.renamed).chain(f.id.aliases.iter()).map(|k| format!("{:?}", k)).collect();
+                writeln!(
+                    w,
+                    "\t\tself.{name} = aliasContainer.contains(anyOf: [{keys}]) ? try aliasContainer.decode({ty}.self, forFirstOf: [{keys}]){conversion} : .absent",
+                    name = f.id.identifier(),
+                    keys = keys.join(", "),
+                    ty = ty,
+                    conversion = conversion
+                )?;
+            }
+            continue;
+        }
+
+        let fallback = match swift_default_value(f, params) {
+            Some(value) if f.has_default && !f.is_optional => format!(" ?? {}", value),
+            _ => "".to_string(),
+        };
+        let method = if swift_is_optional(f, params, rs.direction) || !fallback.is_empty() {
+            "decodeIfPresent"
+        } else {
+            "decode"
+        };
+        let ty = if is_byte_array(f) { "[UInt8]".to_string() } else { swift_base_type(f, params) };
+        let decoded = if !f.id.aliases.is_empty() {
+            let keys: Vec<String> = std::iter::once(&f.id.renamed).chain(f.id.aliases.iter()).map(|k| format!("{:?}", k)).collect();
+            format!("aliasContainer.{}({}.self, forFirstOf: [{}])", method, ty, keys.join(", "))
+        } else {
+            format!("container.{}({}.self, forKey: .{})", method, ty, f.id.identifier())
+        };
+        let decoded = match (is_byte_array(f), method) {
+            (false, _) => decoded,
+            (true, "decode") => format!("Data({})", decoded),
+            (true, _) => format!("{}.map {{ Data($0) }}", decoded),
+        };
+        writeln!(w, "\t\tself.{} = try {}{}", f.id.identifier(), decoded, fallback)?;
+    }
+    writeln!(w, "\t}}")?;
+
+    Ok(())
+}
+
+/// Quoted keys read by the struct's flattened fields, where their types are known.
+fn flattened_keys(rs: &RustStruct, params: &Params) -> Vec<String> {
+    let mut keys = Vec::new();
+    for f in rs.fields.iter().filter(|f| f.is_flattened && !f.is_catch_all()) {
+        if let RustType::Other(path, _) = RustType::parse(&f.ty).resolve(params) {
+            for key in params.object_types.get(&path).into_iter().flatten() {
+                let key = format!("{:?}", key);
+                if !keys.contains(&key) {
+                    keys.push(key);
+                }
+            }
+        }
+    }
+    keys
+}
+
+/// Writes an encoder that merges flattened fields into the parent's keyed container and leaves
+/// absent double options out.
+fn write_encoder(w: &mut dyn Write, params: &Params, rs: &RustStruct) -> std::io::Result<()> {
+    writeln!(w, "\n\tpublic func encode(to encoder: Encoder) throws {{")?;
+    if rs.fields.iter().any(|f| !f.is_flattened && !has_deserialize_name(f)) {
+        writeln!(w, "\t\tvar container = encoder.container(keyedBy: CodingKeys.self)")?;
+    }
+    if rs.fields.iter().any(has_deserialize_name) {
+        writeln!(w, "\t\tvar renamedContainer = encoder.container(keyedBy: {}AnyCodingKey.self)", params.swift_prefix)?;
+    }
+    for f in rs.fields.iter() {
+        let name = f.id.identifier();
+        let value = match (is_byte_array(f), f.is_double_option || swift_is_optional(f, params, rs.direction)) {
+            (false, _) => name.to_string(),
+            (true, false) => format!("[UInt8]({})", name),
+            (true, true) => format!("{}.map {{ [UInt8]($0) }}", name),
+        };
+        if has_deserialize_name(f) {
+            // Rust reads this field under its deserialize name.
+            let method = if swift_is_optional(f, params, rs.direction) { "encodeIfPresent" } else { "encode" };
+            writeln!(
+                w,
+                "\t\ttry renamedContainer.{}({}, forKey: {}AnyCodingKey(stringValue: {:?})!)",
+                method, value, params.swift_prefix, f.id.renamed_deserialize
+            )?;
+            continue;
+        }
+        if f.is_catch_all() {
+            let entries = if f.is_optional { format!("{} ?? [:]", name) } else { name.to_string() };
+            writeln!(w, "\t\tvar {}Container = encoder.container(keyedBy: {}AnyCodingKey.self)", name, params.swift_prefix)?;
+            writeln!(w, "\t\tfor (key, value) in {} {{", entries)?;
+            writeln!(
+                w,
+                "\t\t\ttry {}Container.encode(value, forKey: {}AnyCodingKey(stringValue: key)!)",
+                name, params.swift_prefix
+            )?;
+            writeln!(w, "\t\t}}")?;
+            continue;
+        }
+        if f.is_double_option {
+            writeln!(w, "\t\tif !{0}.isAbsent {{", name)?;
+            writeln!(w, "\t\t\ttry container.encode({}, forKey: .{})", value, name)?;
+            writeln!(w, "\t\t}}")?;
+            continue;
+        }
+        match (f.is_flattened, swift_is_optional(f, params, rs.direction)) {
+            (true, true) => writeln!(w, "\t\ttry {}?.encode(to: encoder)", name)?,
+            (true, false) => writeln!(w, "\t\ttry {}.encode(to: encoder)", name)?,
+            (false, true) => writeln!(w, "\t\ttry container.encodeIfPresent({}, forKey: .{})", value, name)?,
+            (false, false) => writeln!(w, "\t\ttry container.encode({}, forKey: .{})", value, name)?,
+        }
+    }
+    writeln!(w, "\t}}")?;
+
+    Ok(())
+}
+
+/// Writes the coding key used to read and write object keys that are only known at runtime.
+fn write_any_coding_key(w: &mut dyn Write, params: &Params) -> std::io::Result<()> {
+    writeln!(
+        w,
+        "/// Coding key for object keys that are only known at runtime.
+public struct {prefix}AnyCodingKey: CodingKey {{
+	public var stringValue: String
+	public var intValue: Int? {{ return nil }}
+
+	public init?(stringValue: String) {{
+		self.stringValue = stringValue
+	}}
+
+	public init?(intValue: Int) {{
+		return nil
+	}}
+}}
+",
+        prefix = params.swift_prefix
+    )?;
+
+    Ok(())
+}
+
+/// Writes the decoding helpers for fields that accept `#[serde(alias = "...")]` names.
+fn write_alias_decoding(w: &mut dyn Write, params: &Params) -> std::io::Result<()> {
+    writeln!(
+        w,
+        "extension KeyedDecodingContainer where Key == {prefix}AnyCodingKey {{
+	/// Whether any of `keys` is present.
+	func contains(anyOf keys: [String]) -> Bool {{
+		return keys.compactMap(Key.init(stringValue:)).contains(where: contains)
+	}}
+
+	/// Decodes the value stored under the first of `keys` that is present.
+	func decode<T: Decodable>(_ type: T.Type, forFirstOf keys: [String]) throws -> T {{
+		let key = keys.compactMap(Key.init(stringValue:)).first(where: contains) ?? Key(stringValue: keys[0])!
+		return try decode(type, forKey: key)
+	}}
+
+	/// Decodes the value stored under the first of `keys` that is present, or `nil` if there is none.
+	func decodeIfPresent<T: Decodable>(_ type: T.Type, forFirstOf keys: [String]) throws -> T? {{
+		guard let key = keys.compactMap(Key.init(stringValue:)).first(where: contains) else {{
+			return nil
+		}}
+		return try decodeIfPresent(type, forKey: key)
+	}}
+}}
+",
+        prefix = params.swift_prefix
+    )?;
+
+    Ok(())
+}
+
+/// Writes the wrapper for `Option<Option<T>>` fields, which tells a missing key apart from `null`.
+fn write_double_option(w: &mut dyn Write, params: &Params) -> std::io::Result<()> {
+    writeln!(
+        w,
+        "/// A value that is missing, explicitly `null`, or present, like Rust's `Option<Option<T>>`.
+public enum {prefix}DoubleOption<Wrapped: Codable>: Codable {{
+	case absent
+	case null
+	case value(Wrapped)
+
+	public var isAbsent: Bool {{
+		if case .absent = self {{
+			return true
+		}}
+		return false
+	}}
+
+	public func map<T: Codable>(_ transform: (Wrapped) throws -> T) rethrows -> {prefix}DoubleOption<T> {{
+		switch self {{
+		case .absent:
+			return .absent
+		case .null:
+			return .null
+		case .value(let x):
+			return .value(try transform(x))
+		}}
+	}}
+
+	public init(from decoder: Decoder) throws {{
+		let container = try decoder.singleValueContainer()
+		if container.decodeNil() {{
+			self = .null
+		}} else {{
+			self = .value(try container.decode(Wrapped.self))
+		}}
+	}}
+
+	public func encode(to encoder: Encoder) throws {{
+		var container = encoder.singleValueContainer()
+		switch self {{
+		case .absent, .null:
+			try container.encodeNil()
+		case .value(let x):
+			try container.encode(x)
+		}}
+	}}
+}}
+",
+        prefix = params.swift_prefix
+    )?;
+
+    Ok(())
+}
+
+/// Writes the enum for `Result<T, E>`. Swift's own `Result` requires the error to conform to `Error`,
+/// and isn't `Codable`.
+fn write_result(w: &mut dyn Write, params: &Params) -> std::io::Result<()> {
+    writeln!(
+        w,
+        "/// The outcome of a fallible operation, written as `{{\"Ok\": value}}` or `{{\"Err\": error}}` like Rust's `Result<T, E>`.
+public enum {prefix}RustResult<T: Codable, E: Codable>: Codable {{
+	case ok(T)
+	case err(E)
+
+	enum CodingKeys: String, CodingKey {{
+		case ok = \"Ok\"
+		case err = \"Err\"
+	}}
+
+	public init(from decoder: Decoder) throws {{
+		let container = try decoder.container(keyedBy: CodingKeys.self)
+		if container.contains(.ok) {{
+			self = .ok(try container.decode(T.self, forKey: .ok))
+		}} else {{
+			self = .err(try container.decode(E.self, forKey: .err))
+		}}
+	}}
+
+	public func encode(to encoder: Encoder) throws {{
+		var container = encoder.container(keyedBy: CodingKeys.self)
+		switch self {{
+		case .ok(let value):
+			try container.encode(value, forKey: .ok)
+		case .err(let error):
+			try container.encode(error, forKey: .err)
+		}}
+	}}
+}}
+",
+        prefix = params.swift_prefix
+    )?;
+
+    Ok(())
+}
+
+/// Writes the struct for tuples of `arity` elements, which are coded as an array.
+fn write_tuple(w: &mut dyn Write, params: &Params, arity: usize) -> std::io::Result<()> {
+    let indices: Vec<usize> = (0..arity).collect();
+    let generics: Vec<String> = indices.iter().map(|i| format!("T{}: Codable", i)).collect();
+    writeln!(w, "/// A Rust tuple of {} elements, written as a JSON array.", arity)?;
+    writeln!(w, "public struct {}Tuple{}<{}>: Codable {{", params.swift_prefix, arity, generics.join(", "))?;
+    for i in indices.iter() {
+        writeln!(w, "\tpublic let _{0}: T{0}", i)?;
+    }
+
+    let init_params: Vec<String> = indices.iter().map(|i| format!("_ _{0}: T{0}", i)).collect();
+    writeln!(w, "\n\tpublic init({}) {{", init_params.join(", "))?;
+    for i in indices.iter() {
+        writeln!(w, "\t\tself._{0} = _{0}", i)?;
+    }
+    writeln!(w, "\t}}")?;
+
+    writeln!(w, "\n\tpublic init(from decoder: Decoder) throws {{")?;
+    writeln!(w, "\t\tvar container = try decoder.unkeyedContainer()")?;
+    for i in indices.iter() {
+        writeln!(w, "\t\tself._{0} = try container.decode(T{0}.self)", i)?;
+    }
+    writeln!(w, "\t}}")?;
+
+    writeln!(w, "\n\tpublic func encode(to encoder: Encoder) throws {{")?;
+    writeln!(w, "\t\tvar container = encoder.unkeyedContainer()")?;
+    for i in indices.iter() {
+        writeln!(w, "\t\ttry container.encode(_{})", i)?;
+    }
+    writeln!(w, "\t}}")?;
+    writeln!(w, "}}\n")?;
+    Ok(())
+}
+
+/// Writes the struct for Rust's `()`, which is written as `null`.
+fn write_unit(w: &mut dyn Write, params: &Params) -> std::io::Result<()> {
+    writeln!(
+        w,
+        "/// Rust's `()`, written as `null`.
+public struct {prefix}Unit: Codable {{
+	public init() {{}}
+
+	public init(from decoder: Decoder) throws {{
+		guard try decoder.singleValueContainer().decodeNil() else {{
+			throw DecodingError.typeMismatch({prefix}Unit.self, DecodingError.Context(codingPath: decoder.codingPath, debugDescription: \"Expected null\"))
+		}}
+	}}
+
+	public func encode(to encoder: Encoder) throws {{
+		var container = encoder.singleValueContainer()
+		try container.encodeNil()
+	}}
+}}
+",
+        prefix = params.swift_prefix
+    )?;
+
+    Ok(())
+}
+
+/// Writes the enum for `serde_json::Value`, which holds any JSON value.
+fn write_any_codable(w: &mut dyn Write, params: &Params) -> std::io::Result<()> {
+    writeln!(
+        w,
+        "/// Any JSON value, like Rust's `serde_json::Value`.
+public enum {prefix}AnyCodable: Codable, Equatable {{
+	case null
+	case bool(Bool)
+	case number(Double)
+	case string(String)
+	case array([{prefix}AnyCodable])
+	case object([String: {prefix}AnyCodable])
+
+	public init(from decoder: Decoder) throws {{
+		let container = try decoder.singleValueContainer()
+		if container.decodeNil() {{
+			self = .null
+		}} else if let value = try? container.decode(Bool.self) {{
+			self = .bool(value)
+		}} else if let value = try? container.decode(Double.self) {{
+			self = .number(value)
+		}} else if let value = try? container.decode(String.self) {{
+			self = .string(value)
+		}} else if let value = try? container.decode([{prefix}AnyCodable].self) {{
+			self = .array(value)
+		}} else {{
+			self = .object(try container.decode([String: {prefix}AnyCodable].self))
+		}}
+	}}
+
+	public func encode(to encoder: Encoder) throws {{
+		var container = encoder.singleValueContainer()
+		switch self {{
+		case .null:
+			try container.encodeNil()
+		case .bool(let value):
+			try container.encode(value)
+		case .number(let value):
+			try container.encode(value)
+		case .string(let value):
+			try container.encode(value)
+		case .array(let value):
+			try container.encode(value)
+		case .object(let value):
+			try container.encode(value)
+		}}
+	}}
+}}
+",
+        prefix = params.swift_prefix
+    )?;
+
+    Ok(())
+}
+
+/// Writes the dictionary wrapper for maps keyed by integers and enums, which serde writes as JSON
+/// objects with string keys, and the conformances for the key types used.
+fn write_string_keyed(w: &mut dyn Write, params: &Params, keys: &[String]) -> std::io::Result<()> {
+    writeln!(
+        w,
+        "/// A map key written as a string, like Rust's integer and enum keys.
+public protocol {prefix}StringKey: Hashable {{
+	init?(stringKey: String)
+	var stringKey: String {{ get }}
+}}
+
+public extension {prefix}StringKey where Self: LosslessStringConvertible {{
+	init?(stringKey: String) {{
+		self.init(stringKey)
+	}}
+
+	var stringKey: String {{
+		description
+	}}
+}}
+
+public extension {prefix}StringKey where Self: RawRepresentable, RawValue: LosslessStringConvertible {{
+	init?(stringKey: String) {{
+		guard let rawValue = RawValue(stringKey) else {{
+			return nil
+		}}
+		self.init(rawValue: rawValue)
+	}}
+
+	var stringKey: String {{
+		rawValue.description
+	}}
+}}
+
+/// A dictionary written as a JSON object with string keys, where Swift would write an array.
+public struct {prefix}StringKeyed<Key: {prefix}StringKey, Value: Codable>: Codable, ExpressibleByDictionaryLiteral {{
+	public var dictionary: [Key: Value]
+
+	public init(_ dictionary: [Key: Value]) {{
+		self.dictionary = dictionary
+	}}
+
+	public init(dictionaryLiteral elements: (Key, Value)...) {{
+		self.dictionary = Dictionary(uniqueKeysWithValues: elements)
+	}}
+
+	public init(from decoder: Decoder) throws {{
+		let object = try decoder.singleValueContainer().decode([String: Value].self)
+		var dictionary = [Key: Value]()
+		for (stringKey, value) in object {{
+			guard let key = Key(stringKey: stringKey) else {{
+				throw DecodingError.dataCorrupted(DecodingError.Context(codingPath: decoder.codingPath, debugDescription: \"Invalid key: \\(stringKey)\"))
+			}}
+			dictionary[key] = value
+		}}
+		self.dictionary = dictionary
+	}}
+
+	public func encode(to encoder: Encoder) throws {{
+		var container = encoder.singleValueContainer()
+		try container.encode(Dictionary(uniqueKeysWithValues: dictionary.map {{ ($0.key.stringKey, $0.value) }}))
+	}}
+}}
+",
+        prefix = params.swift_prefix
+    )?;
+    for key in keys {
+        writeln!(w, "extension {}: {}StringKey {{}}", key, params.swift_prefix)?;
+    }
+    writeln!(w)?;
+
+    Ok(())
+}
+
+/// Writes the date decoding strategy for chrono's RFC 3339 strings. `.iso8601` rejects fractional
+/// seconds, and `ISO8601DateFormatter` only reads them to the millisecond.
+fn write_date_strategy(w: &mut dyn Write) -> std::io::Result<()> {
+    writeln!(
+        w,
+        "public extension JSONDecoder.DateDecodingStrategy {{
+	/// Reads RFC 3339 dates as Rust's chrono writes them, with or without fractional seconds.
+	static let rfc3339 = custom {{ decoder in
+		var string = try decoder.singleValueContainer().decode(String.self)
+		if let dot = string.firstIndex(of: \".\") {{
+			let digits = string[string.index(after: dot)...].prefix(while: {{ $0.isNumber }})
+			string.replaceSubrange(digits.startIndex..<digits.endIndex, with: (digits + \"000\").prefix(3))
+		}}
+		let formatter = ISO8601DateFormatter()
+		formatter.formatOptions = [.withInternetDateTime, .withFractionalSeconds]
+		if let date = formatter.date(from: string) {{
+			return date
+		}}
+		formatter.formatOptions = [.withInternetDateTime]
+		if let date = formatter.date(from: string) {{
+			return date
+		}}
+		throw DecodingError.dataCorrupted(DecodingError.Context(codingPath: decoder.codingPath, debugDescription: \"Invalid RFC 3339 date: \\(string)\"))
+	}}
+}}
+"
+    )?;
+
+    Ok(())
+}
+
 fn write_struct_convenience_methods(w: &mut dyn Write, generator_params: &Params, rs: &RustStruct) -> std::io::Result<()> {
     let data_init_params = rs
         .fields
         .iter()
-        .map(|f| format!("{param}: decoded.{param}", param = f.id.renamed))
+        .map(|f| format!("{param}: decoded.{param}", param = f.id.identifier()))
         .collect::<Vec<String>>()
         .join(", ");
 
+    let mut seen = vec![rs.id.original.clone()];
+    let has_dates = rs.fields.iter().any(|f| refers_to_dates(&f.rust_type(generator_params), generator_params, &mut seen));
+    let decoder = if has_dates {
+        "let decoder = JSONDecoder()\n\t\tdecoder.dateDecodingStrategy = .rfc3339\n\t\tlet decoded = try decoder"
+    } else {
+        "let decoded = try JSONDecoder()"
+    };
+
     writeln!(
         w,
         "
 public extension {prefix}{struct} {{
 	init(data: Data) throws {{
-		let decoded = try JSONDecoder().decode({prefix}{struct}.self, from: data)
+		{decoder}.decode({prefix}{struct}.self, from: data)
 		self.init({params})
 	}}
 }}
 ",
-        prefix = generator_params.swift_prefix, struct = rs.id.original, params = data_init_params
+        prefix = generator_params.swift_prefix, struct = rs.id.original, decoder = decoder, params = data_init_params
     )?;
 
     Ok(())
 }
 
+/// Whether `ty` holds a `Date`, itself or in the fields of the types it refers to, which decode
+/// with the same strategy as their parent. `seen` holds the types already followed.
+fn refers_to_dates(ty: &RustType, params: &Params, seen: &mut Vec<String>) -> bool {
+    let mut has_dates = false;
+    ty.walk(&mut |ty| {
+        if let RustType::Other(path, _) = ty {
+            if swift_primitive(path) == "Date" {
+                has_dates = true;
+            } else if let (false, Some(types)) = (seen.contains(path), params.type_references.get(path)) {
+                seen.push(path.clone());
+                has_dates |= types.iter().any(|ty| refers_to_dates(ty, params, seen));
+            }
+        }
+    });
+    has_dates
+}
+
+/// `serde_repr` enums are serialized as their `#[repr(...)]` integer type, others as the type of
+/// their discriminants, or by name.
+fn swift_raw_type(e: &RustConstEnum) -> &str {
+    match &e.repr {
+        Some(repr) => swift_primitive(repr),
+        None => swift_lit_type(&e.ty),
+    }
+}
+
+fn swift_raw_value(c: &RustConst) -> String {
+    if let Some(value) = c.repr_value {
+        return value.to_string();
+    }
+    let value = lit_value(&c.value);
+    if value.is_empty() {
+        format!(r##""{}""##, &c.id.renamed)
+    } else {
+        value
+    }
+}
+
+/// `RawRepresentable` decoding throws on values it doesn't know, so enums that may gain cases get
+/// an `unknown` case holding the raw value instead, and an initializer from the raw value that
+/// can't fail.
+fn write_open_enum(w: &mut dyn Write, params: &Params, e: &RustConstEnum) -> std::io::Result<()> {
+    let raw_type = swift_raw_type(e);
+    let cases: Vec<(&RustConst, String)> = e.cases.iter().filter(|c| !c.is_other).map(|c| (c, swift_raw_value(c))).collect();
+    // Named after the cases, such as `unknown_` next to an `unknown` case.
+    let mut unknown = "unknown".to_string();
+    while cases.iter().any(|(c, _)| c.id.identifier() == unknown) {
+        unknown.push('_');
+    }
+
+    writeln!(w, "public enum {}{}: RawRepresentable, Codable, Hashable {{", params.swift_prefix, e.id.original)?;
+    for (c, _) in cases.iter() {
+        write_comments(w, 1, &c.comments)?;
+        writeln!(w, "\tcase {}", c.id.identifier())?;
+    }
+    writeln!(w, "\t/// A case added after this type was generated.")?;
+    writeln!(w, "\tcase {}({})", unknown, raw_type)?;
+
+    writeln!(w, "\n\tpublic init(rawValue: {}) {{", raw_type)?;
+    writeln!(w, "\t\tswitch rawValue {{")?;
+    for (c, value) in cases.iter() {
+        writeln!(w, "\t\tcase {}: self = .{}", value, c.id.identifier())?;
+    }
+    writeln!(w, "\t\tdefault: self = .{}(rawValue)", unknown)?;
+    writeln!(w, "\t\t}}")?;
+    writeln!(w, "\t}}")?;
+
+    writeln!(w, "\n\tpublic var rawValue: {} {{", raw_type)?;
+    writeln!(w, "\t\tswitch self {{")?;
+    for (c, value) in cases.iter() {
+        writeln!(w, "\t\tcase .{}: return {}", c.id.identifier(), value)?;
+    }
+    writeln!(w, "\t\tcase .{}(let value): return value", unknown)?;
+    writeln!(w, "\t\t}}")?;
+    writeln!(w, "\t}}")?;
+
+    writeln!(w, "\n\tpublic init(from decoder: Decoder) throws {{")?;
+    writeln!(w, "\t\tself.init(rawValue: try decoder.singleValueContainer().decode({}.self))", raw_type)?;
+    writeln!(w, "\t}}")?;
+
+    writeln!(w, "\n\tpublic func encode(to encoder: Encoder) throws {{")?;
+    writeln!(w, "\t\tvar container = encoder.singleValueContainer()")?;
+    writeln!(w, "\t\ttry container.encode(rawValue)")?;
+    writeln!(w, "\t}}")?;
+    writeln!(w, "}}\n")?;
+    Ok(())
+}
+
 fn lit_value(l: &Option<syn::ExprLit>) -> String {
     if l.is_none() {
         return "".to_string();
@@ -231,7 +1228,7 @@ fn write_comment(w: &mut dyn Write, indent: usize, comment: &str) -> std::io::Re
 
 fn write_comments(w: &mut dyn Write, indent: usize, comments: &[String]) -> std::io::Result<()> {
     for c in comments {
-        write_comment(w, indent, &c)?;
+        write_comment(w, indent, c)?;
     }
 
     Ok(())
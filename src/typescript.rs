@@ -1,94 +1,216 @@
 use std::io::Write;
 
-use crate::language::{Language, Params, RustAlgebraicEnum, RustConstEnum, RustStruct};
+use crate::config::TypeMapping;
+use crate::language::{
+    map_key, BytesEncoding, EnumTagging, Language, MapKey, Params, RustAlgebraicEnum, RustConst, RustConstEnum, RustField, RustStruct, RustType, TypeScriptLargeIntegers,
+};
 
 pub struct TypeScript {}
 
-fn typescript_type(s: &str) -> &str {
+/// Longest fixed-size array written out as a tuple type; longer ones become plain arrays.
+const MAX_TUPLE_LEN: usize = 16;
+
+fn typescript_type(s: &str, params: &Params) -> String {
+    typescript_type_of(&RustType::parse(s).resolve(params), params)
+}
+
+fn typescript_type_of(ty: &RustType, params: &Params) -> String {
+    if let RustType::Other(path, _) = ty {
+        if let Some(mapped) = params.config.type_mapping(path).and_then(|m| m.ts.as_ref()) {
+            return mapped.clone();
+        }
+    }
+    match ty {
+        RustType::Vec(inner) => typescript_array_type(inner, params),
+        RustType::Array(inner, len) => match len.parse::<usize>() {
+            Ok(len) if len <= MAX_TUPLE_LEN => format!("[{}]", vec![typescript_type_of(inner, params); len].join(", ")),
+            _ => typescript_array_type(inner, params),
+        },
+        RustType::Set(inner) if params.typescript_set => format!("Set<{}>", typescript_type_of(inner, params)),
+        RustType::Set(inner) => typescript_array_type(inner, params),
+        RustType::Map(key, value) => {
+            let value = typescript_type_of(value, params);
+            match map_key(key, params) {
+                MapKey::Enum(_) => format!("Partial<Record<{}, {}>>", typescript_type_of(key, params), value),
+                // `Record` keys can't be `bigint`, and integers sent as strings are keyed by them anyway.
+                MapKey::Integer(int) if typescript_integer_type(&int, params) != "number" => format!("Record<string, {}>", value),
+                _ => format!("Record<{}, {}>", typescript_type_of(key, params), value),
+            }
+        }
+        RustType::Option(inner) => format!("{} | null", typescript_type_of(inner, params)),
+        RustType::Result(ok, err) => format!("{{ Ok: {} }} | {{ Err: {} }}", typescript_type_of(ok, params), typescript_type_of(err, params)),
+        RustType::Tuple(elems) if elems.is_empty() => "null".to_string(),
+        RustType::Tuple(elems) => {
+            let elems: Vec<String> = elems.iter().map(|elem| typescript_type_of(elem, params)).collect();
+            format!("[{}]", elems.join(", "))
+        }
+        RustType::Other(path, args) if args.is_empty() => typescript_integer_type(path, params).to_string(),
+        RustType::Other(path, args) => {
+            let args: Vec<String> = args.iter().map(|arg| typescript_type_of(arg, params)).collect();
+            format!("{}<{}>", path, args.join(", "))
+        }
+    }
+}
+
+fn typescript_array_type(inner: &RustType, params: &Params) -> String {
+    match inner {
+        RustType::Option(_) | RustType::Result(..) => format!("({})[]", typescript_type_of(inner, params)),
+        _ => format!("{}[]", typescript_type_of(inner, params)),
+    }
+}
+
+/// Integers too wide for a `number` follow `typescript_large_integers`.
+fn typescript_integer_type<'a>(s: &'a str, params: &Params) -> &'a str {
+    if !is_large_integer(s) {
+        return typescript_primitive(s);
+    }
+    match params.typescript_large_integers {
+        TypeScriptLargeIntegers::Number => "number",
+        TypeScriptLargeIntegers::BigInt => "bigint",
+        TypeScriptLargeIntegers::String => "string",
+    }
+}
+
+fn is_large_integer(s: &str) -> bool {
+    matches!(s, "i64" | "u64" | "i128" | "u128" | "isize" | "usize")
+}
+
+fn typescript_primitive(s: &str) -> &str {
     match s {
         "str" | "String" => "string",
         "i8" | "i16" | "i32" | "i64" | "i128" => "number",
-        "u8" | "u16" | "u32" | "u64" => "number",
+        "u8" | "u16" | "u32" | "u64" | "u128" => "number",
         "f32" | "f64" => "number",
         "isize" => "number",
         "usize" => "number",
         "bool" => "boolean",
         "char" => "string",
+        "chrono::DateTime" | "chrono::NaiveDateTime" | "chrono::NaiveDate" | "chrono::NaiveTime" => "string",
+        "uuid::Uuid" | "url::Url" => "string",
+        "serde_json::Value" => "unknown",
         _ => s,
     }
 }
 
 impl Language for TypeScript {
-    fn begin_file(&mut self, w: &mut dyn Write, _params: &Params) -> std::io::Result<()> {
+    fn begin_file(&mut self, w: &mut dyn Write, params: &Params) -> std::io::Result<()> {
         writeln!(w, "/*")?;
         writeln!(w, " Generated by typeshare {}", env!("CARGO_PKG_VERSION"))?;
         writeln!(w, "*/")?;
         writeln!(w)?;
+        if !params.config.imports.ts.is_empty() {
+            for line in params.config.imports.ts.iter() {
+                writeln!(w, "{}", line)?;
+            }
+            writeln!(w)?;
+        }
+        if params.typescript_uint8array {
+            write_bytes_helpers(w)?;
+        }
+        if params.typescript_large_integers == TypeScriptLargeIntegers::String {
+            write_large_integer_helpers(w)?;
+        }
         Ok(())
     }
 
-    fn write_struct(&mut self, w: &mut dyn Write, _params: &Params, rs: &RustStruct) -> std::io::Result<()> {
+    fn mapped_type<'a>(&self, mapping: &'a TypeMapping) -> Option<&'a str> {
+        mapping.ts.as_deref()
+    }
+
+    fn lossy_type(&self, ty: &str, params: &Params) -> Option<String> {
+        if is_large_integer(ty) && params.typescript_large_integers == TypeScriptLargeIntegers::Number {
+            Some(format!("`{}` is typed as `number` in TypeScript, which rounds values beyond 2^53", ty))
+        } else {
+            None
+        }
+    }
+
+    fn write_struct(&mut self, w: &mut dyn Write, params: &Params, rs: &RustStruct) -> std::io::Result<()> {
         write_comments(w, 0, &rs.comments)?;
-        writeln!(w, "export interface {} {{", rs.id.original)?;
 
-        for rf in rs.fields.iter() {
+        // Flattened fields contribute their members to this type rather than a property. Interfaces
+        // can only extend object types, so anything else, such as a union, is intersected instead.
+        let flattened: Vec<&RustField> = rs.fields.iter().filter(|rf| rf.is_flattened && !rf.is_catch_all()).collect();
+        let extends: Vec<String> = flattened
+            .iter()
+            .map(|rf| {
+                if rf.is_optional {
+                    format!("Partial<{}>", typescript_type(&rf.ty, params))
+                } else {
+                    typescript_type(&rf.ty, params)
+                }
+            })
+            .collect();
+        let is_intersection = !flattened.iter().all(|rf| is_interface(&RustType::parse(&rf.ty).resolve(params), params));
+        if is_intersection {
+            writeln!(w, "export type {} = {{", rs.id.original)?;
+        } else if extends.is_empty() {
+            writeln!(w, "export interface {} {{", rs.id.original)?;
+        } else {
+            writeln!(w, "export interface {} extends {} {{", rs.id.original, extends.join(", "))?;
+        }
+
+        for rf in rs.fields.iter().filter(|rf| !rf.is_flattened) {
             write_comments(w, 1, &rf.comments)?;
-            if rf.is_vec {
-                writeln!(w, "\t{}{}: {}[];", rf.id.renamed, option_symbol(rf.is_optional), typescript_type(&rf.ty))?;
-            } else if rf.is_hash_map {
-                let map_types: Vec<&str> = rf.ty.split(',').map(|v| v.trim()).collect();
-                writeln!(
-                    w,
-                    "\t{}{}: Map<{}, {}>;",
-                    rf.id.renamed,
-                    option_symbol(rf.is_optional),
-                    typescript_type(map_types.get(0).ok_or(std::io::ErrorKind::InvalidInput)?),
-                    typescript_type(map_types.get(1).ok_or(std::io::ErrorKind::InvalidInput)?)
-                )?;
-            } else {
-                writeln!(w, "\t{}{}: {};", rf.id.renamed, option_symbol(rf.is_optional), typescript_type(&rf.ty))?;
-            }
+            let name = property_name(rf.id.renamed_for(rs.direction));
+            let is_optional = rf.is_omittable(rs.direction);
+            let null = null_union(rf.is_nullable(rs.direction));
+            writeln!(w, "\t{}{}: {}{};", name, option_symbol(is_optional), typescript_field_type(rf, params), null)?;
         }
 
-        writeln!(w, "}}\n")?;
+        // Named properties must be assignable to the index signature, so leave its values untyped.
+        for rf in rs.fields.iter().filter(|rf| rf.is_catch_all()) {
+            write_comments(w, 1, &rf.comments)?;
+            writeln!(w, "\t[key: string]: unknown;")?;
+        }
+
+        if is_intersection {
+            writeln!(w, "}} & {};\n", extends.join(" & "))?;
+        } else {
+            writeln!(w, "}}\n")?;
+        }
+
+        if let (true, Some(keys)) = (rs.deny_unknown_fields, rs.known_keys()) {
+            write_exact_type(w, params, &rs.id.original, &keys)?;
+        }
+        Ok(())
+    }
+
+    fn write_transparent_struct(&mut self, w: &mut dyn Write, params: &Params, rs: &RustStruct) -> std::io::Result<()> {
+        write_comments(w, 0, &rs.comments)?;
+        let rf = &rs.fields[0];
+        writeln!(w, "export type {} = {}{};\n", rs.id.original, typescript_field_type(rf, params), null_union(rf.is_optional))?;
         Ok(())
     }
 
     fn write_const_enum(&mut self, w: &mut dyn Write, _params: &Params, e: &RustConstEnum) -> std::io::Result<()> {
         write_comments(w, 0, &e.comments)?;
+        if e.accepts_unknown {
+            return write_open_enum(w, e);
+        }
         writeln!(w, "export enum {} {{", e.id.original)?;
 
         for c in e.cases.iter() {
-            let mut printed_value = lit_value(&c.value).to_string();
-            if printed_value == "" {
-                printed_value = format!(r##""{}""##, &c.id.renamed);
-            }
-
             write_comments(w, 1, &c.comments)?;
-            writeln!(w, "\t{} = {},", c.id.original, &printed_value)?;
+            writeln!(w, "\t{} = {},", c.id.original, enum_value(c))?;
         }
 
         writeln!(w, "}}\n")?;
         Ok(())
     }
 
-    fn write_algebraic_enum(&mut self, w: &mut dyn Write, _params: &Params, e: &RustAlgebraicEnum) -> std::io::Result<()> {
+    fn write_algebraic_enum(&mut self, w: &mut dyn Write, params: &Params, e: &RustAlgebraicEnum) -> std::io::Result<()> {
         write_comments(w, 0, &e.comments)?;
         write!(w, "export type {} = ", e.id.original)?;
 
         for (index, case) in e.cases.iter().enumerate() {
-            if case.value.is_vec {
-                write!(w, "\n\t| {}[]", typescript_type(&case.value.ty))?;
-            } else if case.value.is_hash_map {
-                let map_types: Vec<&str> = case.value.ty.split(',').map(|v| v.trim()).collect();
-                write!(
-                    w,
-                    "\n\t| Map<{}, {}>",
-                    typescript_type(map_types.get(0).ok_or(std::io::ErrorKind::InvalidInput)?),
-                    typescript_type(map_types.get(1).ok_or(std::io::ErrorKind::InvalidInput)?)
-                )?;
-            } else {
-                write!(w, "\n\t| {}", typescript_type(&case.value.ty))?;
+            let value = typescript_type_of(&case.value.rust_type(params), params);
+            let variant = &case.id.renamed;
+            match &e.tagging {
+                EnumTagging::External => write!(w, "\n\t| {{ {}: {} }}", property_name(variant), value)?,
+                EnumTagging::Internal { tag } => write!(w, "\n\t| ({{ {}: \"{}\" }} & {})", property_name(tag), variant, value)?,
+                EnumTagging::Adjacent { tag, content } => write!(w, "\n\t| {{ {}: \"{}\"; {}: {} }}", property_name(tag), variant, property_name(content), value)?,
+                EnumTagging::Untagged => write!(w, "\n\t| {}", value)?,
             }
             // If we're writing the last of the enum, add the semi-colon
             if index == e.cases.len() - 1 {
@@ -101,6 +223,119 @@ impl Language for TypeScript {
     }
 }
 
+/// `JSON.parse` gives byte buffers as a base64 string or an array of numbers, so `Uint8Array`
+/// fields need converting on the way in and out.
+fn write_bytes_helpers(w: &mut dyn Write) -> std::io::Result<()> {
+    writeln!(
+        w,
+        "export function bytesFromJson(value: string | number[]): Uint8Array {{
+	if (typeof value !== \"string\") {{
+		return Uint8Array.from(value);
+	}}
+	return Uint8Array.from(atob(value), (c) => c.charCodeAt(0));
+}}
+
+export function bytesToBase64(bytes: Uint8Array): string {{
+	return btoa(String.fromCharCode(...bytes));
+}}
+
+export function bytesToArray(bytes: Uint8Array): number[] {{
+	return Array.from(bytes);
+}}
+"
+    )?;
+    Ok(())
+}
+
+/// Large integers sent as strings, for example with `serde_with::DisplayFromStr`, are converted to
+/// and from `bigint` to do arithmetic.
+fn write_large_integer_helpers(w: &mut dyn Write) -> std::io::Result<()> {
+    writeln!(
+        w,
+        "export function parseBigInt(value: string): bigint {{
+	return BigInt(value);
+}}
+
+export function stringifyBigInt(value: bigint): string {{
+	return value.toString();
+}}
+"
+    )?;
+    Ok(())
+}
+
+/// Mirrors `#[serde(deny_unknown_fields)]`: an `Exact` type that rejects extra properties at compile
+/// time and, if enabled, a function that rejects them at runtime.
+fn write_exact_type(w: &mut dyn Write, params: &Params, name: &str, keys: &[&str]) -> std::io::Result<()> {
+    writeln!(w, "export type Exact{0}<T extends {0}> = T & Record<Exclude<keyof T, keyof {0}>, never>;\n", name)?;
+    if !params.typescript_check_unknown_fields {
+        return Ok(());
+    }
+
+    let keys: Vec<String> = keys.iter().map(|k| format!("{:?}", k)).collect();
+    writeln!(w, "export function check{}Fields(value: object): void {{", name)?;
+    writeln!(w, "\tconst known = [{}];", keys.join(", "))?;
+    writeln!(w, "\tconst unknown = Object.keys(value).find((key) => !known.includes(key));")?;
+    writeln!(w, "\tif (unknown !== undefined) {{")?;
+    writeln!(w, "\t\tthrow new Error(`unknown field \\`${{unknown}}\\` in {}`);", name)?;
+    writeln!(w, "\t}}")?;
+    writeln!(w, "}}\n")?;
+    Ok(())
+}
+
+/// A TypeScript enum can't hold values it doesn't declare, so enums that may gain cases become a
+/// constant object of the known values and a type that widens them with `string` (or `number`).
+/// `& {}` keeps the known values suggested by editors.
+fn write_open_enum(w: &mut dyn Write, e: &RustConstEnum) -> std::io::Result<()> {
+    writeln!(w, "export const {} = {{", e.id.original)?;
+    for c in e.cases.iter() {
+        write_comments(w, 1, &c.comments)?;
+        writeln!(w, "\t{}: {},", c.id.original, enum_value(c))?;
+    }
+    writeln!(w, "}} as const;\n")?;
+
+    let widening = match &e.ty {
+        _ if e.repr.is_some() => "number",
+        Some(syn::Lit::Int(_)) | Some(syn::Lit::Float(_)) => "number",
+        _ => "string",
+    };
+    writeln!(w, "export type {0} = (typeof {0})[keyof typeof {0}] | ({1} & {{}});\n", e.id.original, widening)?;
+    Ok(())
+}
+
+/// The value a case is serialized as: its `serde_repr` discriminant, its literal discriminant, or its name.
+fn enum_value(c: &RustConst) -> String {
+    if let Some(value) = c.repr_value {
+        return value.to_string();
+    }
+    let value = lit_value(&c.value);
+    if value.is_empty() {
+        format!(r##""{}""##, &c.id.renamed)
+    } else {
+        value
+    }
+}
+
+/// Whether `ty` is written as an interface, which other interfaces can extend.
+fn is_interface(ty: &RustType, params: &Params) -> bool {
+    match ty {
+        RustType::Other(path, _) => params.object_types.contains_key(path) && params.config.type_mapping(path).and_then(|m| m.ts.as_ref()).is_none(),
+        _ => false,
+    }
+}
+
+fn typescript_field_type(rf: &RustField, params: &Params) -> String {
+    if let Some(ty) = &rf.type_override.ts {
+        return ty.clone();
+    }
+    match rf.bytes {
+        Some(_) if params.typescript_uint8array => "Uint8Array".to_string(),
+        Some(BytesEncoding::Array) => "number[]".to_string(),
+        Some(BytesEncoding::Base64) => "string".to_string(),
+        None => typescript_type_of(&rf.rust_type(params), params),
+    }
+}
+
 fn bool_literal(b: bool) -> &'static str {
     if b {
         "true"
@@ -109,6 +344,17 @@ fn bool_literal(b: bool) -> &'static str {
     }
 }
 
+/// Quotes names that aren't valid identifiers, such as those produced by `kebab-case`.
+fn property_name(name: &str) -> String {
+    let mut chars = name.chars();
+    let is_identifier = chars.next().is_some_and(|c| c.is_alphabetic() || c == '_' || c == '$') && chars.all(|c| c.is_alphanumeric() || c == '_' || c == '$');
+    if is_identifier {
+        name.to_string()
+    } else {
+        format!("\"{}\"", name)
+    }
+}
+
 fn option_symbol(optional: bool) -> &'static str {
     if optional {
         "?"
@@ -117,6 +363,14 @@ fn option_symbol(optional: bool) -> &'static str {
     }
 }
 
+fn null_union(nullable: bool) -> &'static str {
+    if nullable {
+        " | null"
+    } else {
+        ""
+    }
+}
+
 fn lit_value(l: &Option<syn::ExprLit>) -> String {
     if l.is_none() {
         return "".to_string();
@@ -147,7 +401,7 @@ fn write_comment(w: &mut dyn Write, indent: usize, inline: bool, comment: &str)
 
 fn write_comments(w: &mut dyn Write, indent: usize, comments: &[String]) -> std::io::Result<()> {
     for c in comments {
-        write_comment(w, indent, false, &c)?;
+        write_comment(w, indent, false, c)?;
     }
 
     Ok(())
@@ -155,7 +409,7 @@ fn write_comments(w: &mut dyn Write, indent: usize, comments: &[String]) -> std:
 
 fn write_comments_inline(w: &mut dyn Write, comments: &[String]) -> std::io::Result<()> {
     for c in comments {
-        write_comment(w, 1, true, &c)?;
+        write_comment(w, 1, true, c)?;
     }
 
     Ok(())
@@ -0,0 +1,59 @@
+use typeshare::case::RenameRule;
+
+const RULES: &[&str] = &[
+    "lowercase",
+    "UPPERCASE",
+    "PascalCase",
+    "camelCase",
+    "snake_case",
+    "SCREAMING_SNAKE_CASE",
+    "kebab-case",
+    "SCREAMING-KEBAB-CASE",
+];
+
+// The same table serde_derive checks its own rules against, one column per entry in `RULES`.
+#[test]
+fn renames_variants_like_serde() {
+    let table = &[
+        ("Outcome", ["outcome", "OUTCOME", "Outcome", "outcome", "outcome", "OUTCOME", "outcome", "OUTCOME"]),
+        (
+            "VeryTasty",
+            ["verytasty", "VERYTASTY", "VeryTasty", "veryTasty", "very_tasty", "VERY_TASTY", "very-tasty", "VERY-TASTY"],
+        ),
+        ("A", ["a", "A", "A", "a", "a", "A", "a", "A"]),
+        ("Z42", ["z42", "Z42", "Z42", "z42", "z42", "Z42", "z42", "Z42"]),
+    ];
+
+    for (original, expected) in table {
+        for (rule, expected) in RULES.iter().zip(expected.iter()) {
+            let renamed = RenameRule::from_name(rule).unwrap().apply_to_variant(original);
+            assert_eq!(*expected, renamed, "{} with {}", original, rule);
+        }
+    }
+}
+
+#[test]
+fn renames_fields_like_serde() {
+    let table = &[
+        ("outcome", ["outcome", "OUTCOME", "Outcome", "outcome", "outcome", "OUTCOME", "outcome", "OUTCOME"]),
+        (
+            "very_tasty",
+            ["very_tasty", "VERY_TASTY", "VeryTasty", "veryTasty", "very_tasty", "VERY_TASTY", "very-tasty", "VERY-TASTY"],
+        ),
+        ("a", ["a", "A", "A", "a", "a", "A", "a", "A"]),
+        ("z42", ["z42", "Z42", "Z42", "z42", "z42", "Z42", "z42", "Z42"]),
+    ];
+
+    for (original, expected) in table {
+        for (rule, expected) in RULES.iter().zip(expected.iter()) {
+            let renamed = RenameRule::from_name(rule).unwrap().apply_to_field(original);
+            assert_eq!(*expected, renamed, "{} with {}", original, rule);
+        }
+    }
+}
+
+#[test]
+fn rejects_unknown_rules() {
+    assert_eq!(None, RenameRule::from_name("Train-Case"));
+    assert_eq!(None, RenameRule::from_name("camelcase"));
+}
@@ -0,0 +1,1434 @@
+use typeshare::java;
+use typeshare::language::{Generator, JavaLargeIntegers, Params};
+
+#[test]
+fn can_name_properties_after_non_identifier_keys() {
+    let mut lang = java::Java::new();
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "com.example".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[serde(rename_all = "kebab-case")]
+pub struct Header {
+    pub content_type: String,
+    #[serde(rename = "2fa")]
+    pub two_factor: bool,
+}
+
+#[serde(rename_all = "kebab-case")]
+pub enum Level {
+    VeryHigh,
+    Low,
+}
+
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum Mode {
+    ReadOnly,
+}
+
+#[serde(rename_all = "SCREAMING-KEBAB-CASE")]
+#[serde(deny_unknown_fields)]
+pub struct Strict {
+    pub max_size: u32,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        r#"/**
+ * Generated by typeshare {}
+ */
+
+package com.example;
+
+import com.fasterxml.jackson.annotation.*;
+
+/**
+ */
+public class Header {{
+	private String contentType;
+	private boolean _2fa;
+
+	/**
+	 */
+	@JsonProperty("content-type")
+	public String getContentType() {{ return contentType; }}
+	@JsonProperty("content-type")
+	public void setContentType(String value) {{ this.contentType = value; }}
+
+	/**
+	 */
+	@JsonProperty("2fa")
+	public boolean get2Fa() {{ return _2fa; }}
+	@JsonProperty("2fa")
+	public void set2Fa(boolean value) {{ this._2fa = value; }}
+
+}}
+
+package com.example;
+
+import com.fasterxml.jackson.annotation.*;
+
+/**
+ */
+@JsonIgnoreProperties(ignoreUnknown = false)
+public class Strict {{
+	private long maxSize;
+
+	/**
+	 */
+	@JsonProperty("MAX-SIZE")
+	public long getMaxSize() {{ return maxSize; }}
+	@JsonProperty("MAX-SIZE")
+	public void setMaxSize(long value) {{ this.maxSize = value; }}
+
+}}
+
+package com.example;
+
+import com.fasterxml.jackson.annotation.*;
+
+/**
+ */
+public enum Level {{
+	@JsonProperty("very-high")
+	VeryHigh,
+	@JsonProperty("low")
+	Low,
+}}
+
+package com.example;
+
+import com.fasterxml.jackson.annotation.*;
+
+/**
+ */
+public enum Mode {{
+	@JsonProperty("read-only")
+	ReadOnly,
+	/**
+	 * A case added after this type was generated.
+	 */
+	@JsonEnumDefaultValue
+	Unknown,
+}}
+
+package com.example;
+
+import java.io.IOException;
+import com.fasterxml.jackson.databind.*;
+import com.fasterxml.jackson.core.JsonProcessingException;
+
+/**
+ * Serialize/Deserialize helpers
+ */
+public class Converter {{
+
+	public static Header HeaderFromJsonString(String json) throws IOException {{
+		return getHeaderObjectReader().readValue(json);
+	}}
+
+	public static String HeaderToJsonString(Header obj) throws JsonProcessingException {{
+		return getHeaderObjectWriter().writeValueAsString(obj);
+	}}
+
+	public static Strict StrictFromJsonString(String json) throws IOException {{
+		return getStrictObjectReader().readValue(json);
+	}}
+
+	public static String StrictToJsonString(Strict obj) throws JsonProcessingException {{
+		return getStrictObjectWriter().writeValueAsString(obj);
+	}}
+
+	private static ObjectReader HeaderReader;
+	private static ObjectWriter HeaderWriter;
+
+	private static void instantiateHeaderMapper() {{
+		ObjectMapper mapper = new ObjectMapper();
+		mapper.enable(DeserializationFeature.READ_UNKNOWN_ENUM_VALUES_USING_DEFAULT_VALUE);
+		HeaderReader = mapper.readerFor(Header.class);
+		HeaderWriter = mapper.writerFor(Header.class);
+	}}
+
+	private static ObjectReader getHeaderObjectReader() {{
+		if (HeaderReader == null) instantiateHeaderMapper();
+		return HeaderReader;
+	}}
+
+	private static ObjectWriter getHeaderObjectWriter() {{
+		if (HeaderWriter == null) instantiateHeaderMapper();
+		return HeaderWriter;
+	}}
+
+	private static ObjectReader StrictReader;
+	private static ObjectWriter StrictWriter;
+
+	private static void instantiateStrictMapper() {{
+		ObjectMapper mapper = new ObjectMapper();
+		mapper.enable(DeserializationFeature.READ_UNKNOWN_ENUM_VALUES_USING_DEFAULT_VALUE);
+		StrictReader = mapper.readerFor(Strict.class);
+		StrictWriter = mapper.writerFor(Strict.class);
+	}}
+
+	private static ObjectReader getStrictObjectReader() {{
+		if (StrictReader == null) instantiateStrictMapper();
+		return StrictReader;
+	}}
+
+	private static ObjectWriter getStrictObjectWriter() {{
+		if (StrictWriter == null) instantiateStrictMapper();
+		return StrictWriter;
+	}}
+
+}}
+"#,
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_map_results() {
+    let mut lang = java::Java::new();
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "com.example".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[typeshare]
+#[derive(Serialize, Deserialize)]
+pub struct Unlock {
+    pub outcome: Result<Vault, ApiError>,
+    pub retried: Option<Result<bool, String>>,
+    pub batch: Vec<Result<u32, String>>,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        r#"/**
+ * Generated by typeshare {}
+ */
+
+package com.example;
+
+import com.fasterxml.jackson.annotation.*;
+
+/**
+ */
+public class Unlock {{
+	private RustResult<Vault, ApiError> outcome;
+	private RustResult<Boolean, String> retried;
+	private RustResult<Long, String> batch[];
+
+	/**
+	 */
+	@JsonProperty("outcome")
+	public RustResult<Vault, ApiError> getOutcome() {{ return outcome; }}
+	@JsonProperty("outcome")
+	public void setOutcome(RustResult<Vault, ApiError> value) {{ this.outcome = value; }}
+
+	/**
+	 */
+	@JsonProperty("retried")
+	public RustResult<Boolean, String> getRetried() {{ return retried; }}
+	@JsonProperty("retried")
+	public void setRetried(RustResult<Boolean, String> value) {{ this.retried = value; }}
+
+	/**
+	 */
+	@JsonProperty("batch")
+	public RustResult<Long, String>[] getBatch() {{ return batch; }}
+	@JsonProperty("batch")
+	public void setBatch(RustResult<Long, String>[] value) {{ this.batch = value; }}
+
+}}
+
+package com.example;
+
+import com.fasterxml.jackson.annotation.*;
+
+/**
+ * The outcome of a fallible operation, written as {{"Ok": value}} or {{"Err": error}} like Rust's Result<T, E>.
+ */
+public class RustResult<T, E> {{
+	private T ok;
+	private E err;
+	private boolean isErr;
+
+	@JsonAnyGetter
+	public java.util.Map<String, Object> toJson() {{ return java.util.Collections.singletonMap(isErr ? "Err" : "Ok", isErr ? err : ok); }}
+
+	@JsonIgnore
+	public T getOk() {{ return ok; }}
+	@JsonProperty("Ok")
+	public void setOk(T value) {{ this.ok = value; this.err = null; this.isErr = false; }}
+
+	@JsonIgnore
+	public E getErr() {{ return err; }}
+	@JsonProperty("Err")
+	public void setErr(E value) {{ this.err = value; this.ok = null; this.isErr = true; }}
+
+	@JsonIgnore
+	public boolean isErr() {{ return isErr; }}
+}}
+
+package com.example;
+
+import java.io.IOException;
+import com.fasterxml.jackson.databind.*;
+import com.fasterxml.jackson.core.JsonProcessingException;
+
+/**
+ * Serialize/Deserialize helpers
+ */
+public class Converter {{
+
+	public static Unlock UnlockFromJsonString(String json) throws IOException {{
+		return getUnlockObjectReader().readValue(json);
+	}}
+
+	public static String UnlockToJsonString(Unlock obj) throws JsonProcessingException {{
+		return getUnlockObjectWriter().writeValueAsString(obj);
+	}}
+
+	private static ObjectReader UnlockReader;
+	private static ObjectWriter UnlockWriter;
+
+	private static void instantiateUnlockMapper() {{
+		ObjectMapper mapper = new ObjectMapper();
+		mapper.enable(DeserializationFeature.READ_UNKNOWN_ENUM_VALUES_USING_DEFAULT_VALUE);
+		UnlockReader = mapper.readerFor(Unlock.class);
+		UnlockWriter = mapper.writerFor(Unlock.class);
+	}}
+
+	private static ObjectReader getUnlockObjectReader() {{
+		if (UnlockReader == null) instantiateUnlockMapper();
+		return UnlockReader;
+	}}
+
+	private static ObjectWriter getUnlockObjectWriter() {{
+		if (UnlockWriter == null) instantiateUnlockMapper();
+		return UnlockWriter;
+	}}
+
+}}
+"#,
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_map_tuples() {
+    let mut lang = java::Java::new();
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "com.example".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[typeshare]
+#[derive(Serialize, Deserialize)]
+pub struct Route {
+    pub endpoints: (String, u16),
+    pub waypoints: Vec<(f64, f64)>,
+    pub label: Option<(String, Option<u32>, bool)>,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        r#"/**
+ * Generated by typeshare {}
+ */
+
+package com.example;
+
+import com.fasterxml.jackson.annotation.*;
+
+/**
+ */
+public class Route {{
+	private Tuple2<String, Integer> endpoints;
+	private Tuple2<Double, Double> waypoints[];
+	private Tuple3<String, Long, Boolean> label;
+
+	/**
+	 */
+	@JsonProperty("endpoints")
+	public Tuple2<String, Integer> getEndpoints() {{ return endpoints; }}
+	@JsonProperty("endpoints")
+	public void setEndpoints(Tuple2<String, Integer> value) {{ this.endpoints = value; }}
+
+	/**
+	 */
+	@JsonProperty("waypoints")
+	public Tuple2<Double, Double>[] getWaypoints() {{ return waypoints; }}
+	@JsonProperty("waypoints")
+	public void setWaypoints(Tuple2<Double, Double>[] value) {{ this.waypoints = value; }}
+
+	/**
+	 */
+	@JsonProperty("label")
+	public Tuple3<String, Long, Boolean> getLabel() {{ return label; }}
+	@JsonProperty("label")
+	public void setLabel(Tuple3<String, Long, Boolean> value) {{ this.label = value; }}
+
+}}
+
+package com.example;
+
+import com.fasterxml.jackson.annotation.*;
+
+/**
+ * A Rust tuple of 2 elements, written as a JSON array.
+ */
+@JsonFormat(shape = JsonFormat.Shape.ARRAY)
+@JsonPropertyOrder({{"_0", "_1"}})
+public class Tuple2<T0, T1> {{
+	private T0 _0;
+	private T1 _1;
+
+	@JsonProperty("_0")
+	public T0 get_0() {{ return _0; }}
+	@JsonProperty("_0")
+	public void set_0(T0 value) {{ this._0 = value; }}
+
+	@JsonProperty("_1")
+	public T1 get_1() {{ return _1; }}
+	@JsonProperty("_1")
+	public void set_1(T1 value) {{ this._1 = value; }}
+}}
+
+package com.example;
+
+import com.fasterxml.jackson.annotation.*;
+
+/**
+ * A Rust tuple of 3 elements, written as a JSON array.
+ */
+@JsonFormat(shape = JsonFormat.Shape.ARRAY)
+@JsonPropertyOrder({{"_0", "_1", "_2"}})
+public class Tuple3<T0, T1, T2> {{
+	private T0 _0;
+	private T1 _1;
+	private T2 _2;
+
+	@JsonProperty("_0")
+	public T0 get_0() {{ return _0; }}
+	@JsonProperty("_0")
+	public void set_0(T0 value) {{ this._0 = value; }}
+
+	@JsonProperty("_1")
+	public T1 get_1() {{ return _1; }}
+	@JsonProperty("_1")
+	public void set_1(T1 value) {{ this._1 = value; }}
+
+	@JsonProperty("_2")
+	public T2 get_2() {{ return _2; }}
+	@JsonProperty("_2")
+	public void set_2(T2 value) {{ this._2 = value; }}
+}}
+
+package com.example;
+
+import java.io.IOException;
+import com.fasterxml.jackson.databind.*;
+import com.fasterxml.jackson.core.JsonProcessingException;
+
+/**
+ * Serialize/Deserialize helpers
+ */
+public class Converter {{
+
+	public static Route RouteFromJsonString(String json) throws IOException {{
+		return getRouteObjectReader().readValue(json);
+	}}
+
+	public static String RouteToJsonString(Route obj) throws JsonProcessingException {{
+		return getRouteObjectWriter().writeValueAsString(obj);
+	}}
+
+	private static ObjectReader RouteReader;
+	private static ObjectWriter RouteWriter;
+
+	private static void instantiateRouteMapper() {{
+		ObjectMapper mapper = new ObjectMapper();
+		mapper.enable(DeserializationFeature.READ_UNKNOWN_ENUM_VALUES_USING_DEFAULT_VALUE);
+		RouteReader = mapper.readerFor(Route.class);
+		RouteWriter = mapper.writerFor(Route.class);
+	}}
+
+	private static ObjectReader getRouteObjectReader() {{
+		if (RouteReader == null) instantiateRouteMapper();
+		return RouteReader;
+	}}
+
+	private static ObjectWriter getRouteObjectWriter() {{
+		if (RouteWriter == null) instantiateRouteMapper();
+		return RouteWriter;
+	}}
+
+}}
+"#,
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_type_large_integers_as_long() {
+    let mut lang = java::Java::new();
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "com.example".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[typeshare]
+pub struct Counters {
+    pub small: u32,
+    pub total: u64,
+    pub len: usize,
+    pub by_id: HashMap<String, u64>,
+    pub history: Vec<u64>,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        r#"/**
+ * Generated by typeshare {}
+ */
+
+package com.example;
+
+import com.fasterxml.jackson.annotation.*;
+
+/**
+ */
+public class Counters {{
+	private long small;
+	private long total;
+	private long len;
+	private java.util.Map<String, Long> by_id;
+	private long history[];
+
+	/**
+	 */
+	@JsonProperty("small")
+	public long getSmall() {{ return small; }}
+	@JsonProperty("small")
+	public void setSmall(long value) {{ this.small = value; }}
+
+	/**
+	 */
+	@JsonProperty("total")
+	public long getTotal() {{ return total; }}
+	@JsonProperty("total")
+	public void setTotal(long value) {{ this.total = value; }}
+
+	/**
+	 */
+	@JsonProperty("len")
+	public long getLen() {{ return len; }}
+	@JsonProperty("len")
+	public void setLen(long value) {{ this.len = value; }}
+
+	/**
+	 */
+	@JsonProperty("by_id")
+	public java.util.Map<String, Long> getById() {{ return by_id; }}
+	@JsonProperty("by_id")
+	public void setById(java.util.Map<String, Long> value) {{ this.by_id = value; }}
+
+	/**
+	 */
+	@JsonProperty("history")
+	public long[] getHistory() {{ return history; }}
+	@JsonProperty("history")
+	public void setHistory(long[] value) {{ this.history = value; }}
+
+}}
+
+package com.example;
+
+import java.io.IOException;
+import com.fasterxml.jackson.databind.*;
+import com.fasterxml.jackson.core.JsonProcessingException;
+
+/**
+ * Serialize/Deserialize helpers
+ */
+public class Converter {{
+
+	public static Counters CountersFromJsonString(String json) throws IOException {{
+		return getCountersObjectReader().readValue(json);
+	}}
+
+	public static String CountersToJsonString(Counters obj) throws JsonProcessingException {{
+		return getCountersObjectWriter().writeValueAsString(obj);
+	}}
+
+	private static ObjectReader CountersReader;
+	private static ObjectWriter CountersWriter;
+
+	private static void instantiateCountersMapper() {{
+		ObjectMapper mapper = new ObjectMapper();
+		mapper.enable(DeserializationFeature.READ_UNKNOWN_ENUM_VALUES_USING_DEFAULT_VALUE);
+		CountersReader = mapper.readerFor(Counters.class);
+		CountersWriter = mapper.writerFor(Counters.class);
+	}}
+
+	private static ObjectReader getCountersObjectReader() {{
+		if (CountersReader == null) instantiateCountersMapper();
+		return CountersReader;
+	}}
+
+	private static ObjectWriter getCountersObjectWriter() {{
+		if (CountersWriter == null) instantiateCountersMapper();
+		return CountersWriter;
+	}}
+
+}}
+"#,
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_type_large_integers_as_big_integer() {
+    let mut lang = java::Java::new();
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "com.example".to_string(),
+            java_large_integers: JavaLargeIntegers::BigInteger,
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[typeshare]
+pub struct Counters {
+    pub small: u32,
+    pub total: u64,
+    pub len: usize,
+    pub by_id: HashMap<String, u64>,
+    pub history: Vec<u64>,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        r#"/**
+ * Generated by typeshare {}
+ */
+
+package com.example;
+
+import com.fasterxml.jackson.annotation.*;
+
+/**
+ */
+public class Counters {{
+	private long small;
+	private java.math.BigInteger total;
+	private java.math.BigInteger len;
+	private java.util.Map<String, java.math.BigInteger> by_id;
+	private java.math.BigInteger history[];
+
+	/**
+	 */
+	@JsonProperty("small")
+	public long getSmall() {{ return small; }}
+	@JsonProperty("small")
+	public void setSmall(long value) {{ this.small = value; }}
+
+	/**
+	 */
+	@JsonProperty("total")
+	public java.math.BigInteger getTotal() {{ return total; }}
+	@JsonProperty("total")
+	public void setTotal(java.math.BigInteger value) {{ this.total = value; }}
+
+	/**
+	 */
+	@JsonProperty("len")
+	public java.math.BigInteger getLen() {{ return len; }}
+	@JsonProperty("len")
+	public void setLen(java.math.BigInteger value) {{ this.len = value; }}
+
+	/**
+	 */
+	@JsonProperty("by_id")
+	public java.util.Map<String, java.math.BigInteger> getById() {{ return by_id; }}
+	@JsonProperty("by_id")
+	public void setById(java.util.Map<String, java.math.BigInteger> value) {{ this.by_id = value; }}
+
+	/**
+	 */
+	@JsonProperty("history")
+	public java.math.BigInteger[] getHistory() {{ return history; }}
+	@JsonProperty("history")
+	public void setHistory(java.math.BigInteger[] value) {{ this.history = value; }}
+
+}}
+
+package com.example;
+
+import java.io.IOException;
+import com.fasterxml.jackson.databind.*;
+import com.fasterxml.jackson.core.JsonProcessingException;
+
+/**
+ * Serialize/Deserialize helpers
+ */
+public class Converter {{
+
+	public static Counters CountersFromJsonString(String json) throws IOException {{
+		return getCountersObjectReader().readValue(json);
+	}}
+
+	public static String CountersToJsonString(Counters obj) throws JsonProcessingException {{
+		return getCountersObjectWriter().writeValueAsString(obj);
+	}}
+
+	private static ObjectReader CountersReader;
+	private static ObjectWriter CountersWriter;
+
+	private static void instantiateCountersMapper() {{
+		ObjectMapper mapper = new ObjectMapper();
+		mapper.enable(DeserializationFeature.READ_UNKNOWN_ENUM_VALUES_USING_DEFAULT_VALUE);
+		CountersReader = mapper.readerFor(Counters.class);
+		CountersWriter = mapper.writerFor(Counters.class);
+	}}
+
+	private static ObjectReader getCountersObjectReader() {{
+		if (CountersReader == null) instantiateCountersMapper();
+		return CountersReader;
+	}}
+
+	private static ObjectWriter getCountersObjectWriter() {{
+		if (CountersWriter == null) instantiateCountersMapper();
+		return CountersWriter;
+	}}
+
+}}
+"#,
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn warns_about_large_integers_typed_as_long() {
+    let mut lang = java::Java::new();
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "com.example".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[typeshare]
+pub struct Counters {
+    pub small: u32,
+    pub by_id: HashMap<String, Vec<u64>>,
+    #[typeshare(java = "String")]
+    pub total: u64,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    assert_eq!(
+        g.warnings(),
+        ["Counters.by_id: `u64` is typed as `long` in Java, which can't hold values of 2^63 and above"]
+    );
+}
+
+#[test]
+fn keeps_large_integers_whole_as_big_integer() {
+    let mut lang = java::Java::new();
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "com.example".to_string(),
+            java_large_integers: JavaLargeIntegers::BigInteger,
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[typeshare]
+pub struct Counters {
+    pub by_id: HashMap<String, Vec<u64>>,
+    pub len: usize,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    assert!(g.warnings().is_empty(), "BigInteger holds every integer");
+}
+
+#[test]
+fn can_name_the_unknown_case_apart_from_user_cases() {
+    let mut lang = java::Java::new();
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "com.example".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[non_exhaustive]
+pub enum Status {
+    Known,
+    Unknown,
+}
+
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum Health {
+    Healthy,
+    Unknown,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        r#"/**
+ * Generated by typeshare {}
+ */
+
+package com.example;
+
+import com.fasterxml.jackson.annotation.*;
+
+/**
+ */
+public enum Status {{
+	@JsonProperty("Known")
+	Known,
+	@JsonProperty("Unknown")
+	Unknown,
+	/**
+	 * A case added after this type was generated.
+	 */
+	@JsonEnumDefaultValue
+	Unknown_,
+}}
+
+package com.example;
+
+import com.fasterxml.jackson.annotation.*;
+
+/**
+ */
+public enum Health {{
+	@JsonProperty("healthy")
+	Healthy,
+	@JsonProperty("unknown")
+	Unknown,
+	/**
+	 * A case added after this type was generated.
+	 */
+	@JsonEnumDefaultValue
+	Unknown_,
+}}
+
+package com.example;
+
+import java.io.IOException;
+import com.fasterxml.jackson.databind.*;
+import com.fasterxml.jackson.core.JsonProcessingException;
+
+/**
+ * Serialize/Deserialize helpers
+ */
+public class Converter {{
+
+}}
+"#,
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[cfg(all(feature = "uuid", feature = "chrono"))]
+#[test]
+fn can_initialise_defaulted_fields() {
+    let mut lang = java::Java::new();
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "com.example".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[derive(Default)]
+pub struct Inner {
+    pub a: u32,
+}
+
+#[serde(transparent)]
+pub struct Blob(Vec<u8>);
+
+pub enum Kind {
+    A,
+    B,
+}
+
+#[serde(default)]
+pub struct Settings {
+    pub name: String,
+    pub retries: u32,
+    pub limit: Option<u32>,
+    pub tags: Vec<String>,
+    pub labels: HashMap<String, String>,
+    pub inner: Inner,
+    pub kind: Kind,
+    pub id: Uuid,
+    pub at: DateTime<Utc>,
+    pub pair: (u32, String),
+    pub outcome: Result<u32, String>,
+    pub blob: Blob,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        r#"/**
+ * Generated by typeshare {}
+ */
+
+package com.example;
+
+import com.fasterxml.jackson.annotation.*;
+
+/**
+ */
+public class Inner {{
+	private long a;
+
+	/**
+	 */
+	@JsonProperty("a")
+	public long getA() {{ return a; }}
+	@JsonProperty("a")
+	public void setA(long value) {{ this.a = value; }}
+
+}}
+
+package com.example;
+
+import com.fasterxml.jackson.annotation.*;
+
+/**
+ */
+public class Blob {{
+	private int[] value;
+
+	@JsonCreator
+	public Blob(int[] value) {{ this.value = value; }}
+
+	@JsonValue
+	public int[] getValue() {{ return value; }}
+}}
+
+package com.example;
+
+import com.fasterxml.jackson.annotation.*;
+
+/**
+ */
+public class Settings {{
+	private String name = "";
+	private long retries = 0;
+	private Long limit = null;
+	private String tags[] = new String[0];
+	private java.util.Map<String, String> labels = new java.util.HashMap<>();
+	private Inner inner = new Inner();
+	private Kind kind;
+	private java.util.UUID id;
+	private java.time.OffsetDateTime at;
+	private Tuple2<Long, String> pair;
+	private RustResult<Long, String> outcome;
+	private Blob blob;
+
+	/**
+	 */
+	@JsonProperty("name")
+	public String getName() {{ return name; }}
+	@JsonProperty("name")
+	public void setName(String value) {{ this.name = value; }}
+
+	/**
+	 */
+	@JsonProperty("retries")
+	public long getRetries() {{ return retries; }}
+	@JsonProperty("retries")
+	public void setRetries(long value) {{ this.retries = value; }}
+
+	/**
+	 */
+	@JsonProperty("limit")
+	public Long getLimit() {{ return limit; }}
+	@JsonProperty("limit")
+	public void setLimit(Long value) {{ this.limit = value; }}
+
+	/**
+	 */
+	@JsonProperty("tags")
+	public String[] getTags() {{ return tags; }}
+	@JsonProperty("tags")
+	public void setTags(String[] value) {{ this.tags = value; }}
+
+	/**
+	 */
+	@JsonProperty("labels")
+	public java.util.Map<String, String> getLabels() {{ return labels; }}
+	@JsonProperty("labels")
+	public void setLabels(java.util.Map<String, String> value) {{ this.labels = value; }}
+
+	/**
+	 */
+	@JsonProperty("inner")
+	public Inner getInner() {{ return inner; }}
+	@JsonProperty("inner")
+	public void setInner(Inner value) {{ this.inner = value; }}
+
+	/**
+	 */
+	@JsonProperty("kind")
+	public Kind getKind() {{ return kind; }}
+	@JsonProperty("kind")
+	public void setKind(Kind value) {{ this.kind = value; }}
+
+	/**
+	 */
+	@JsonProperty("id")
+	public java.util.UUID getID() {{ return id; }}
+	@JsonProperty("id")
+	public void setID(java.util.UUID value) {{ this.id = value; }}
+
+	/**
+	 */
+	@JsonProperty("at")
+	public java.time.OffsetDateTime getAt() {{ return at; }}
+	@JsonProperty("at")
+	public void setAt(java.time.OffsetDateTime value) {{ this.at = value; }}
+
+	/**
+	 */
+	@JsonProperty("pair")
+	public Tuple2<Long, String> getPair() {{ return pair; }}
+	@JsonProperty("pair")
+	public void setPair(Tuple2<Long, String> value) {{ this.pair = value; }}
+
+	/**
+	 */
+	@JsonProperty("outcome")
+	public RustResult<Long, String> getOutcome() {{ return outcome; }}
+	@JsonProperty("outcome")
+	public void setOutcome(RustResult<Long, String> value) {{ this.outcome = value; }}
+
+	/**
+	 */
+	@JsonProperty("blob")
+	public Blob getBlob() {{ return blob; }}
+	@JsonProperty("blob")
+	public void setBlob(Blob value) {{ this.blob = value; }}
+
+}}
+
+package com.example;
+
+import com.fasterxml.jackson.annotation.*;
+
+/**
+ */
+public enum Kind {{
+	@JsonProperty("A")
+	A,
+	@JsonProperty("B")
+	B,
+}}
+
+package com.example;
+
+import com.fasterxml.jackson.annotation.*;
+
+/**
+ * The outcome of a fallible operation, written as {{"Ok": value}} or {{"Err": error}} like Rust's Result<T, E>.
+ */
+public class RustResult<T, E> {{
+	private T ok;
+	private E err;
+	private boolean isErr;
+
+	@JsonAnyGetter
+	public java.util.Map<String, Object> toJson() {{ return java.util.Collections.singletonMap(isErr ? "Err" : "Ok", isErr ? err : ok); }}
+
+	@JsonIgnore
+	public T getOk() {{ return ok; }}
+	@JsonProperty("Ok")
+	public void setOk(T value) {{ this.ok = value; this.err = null; this.isErr = false; }}
+
+	@JsonIgnore
+	public E getErr() {{ return err; }}
+	@JsonProperty("Err")
+	public void setErr(E value) {{ this.err = value; this.ok = null; this.isErr = true; }}
+
+	@JsonIgnore
+	public boolean isErr() {{ return isErr; }}
+}}
+
+package com.example;
+
+import com.fasterxml.jackson.annotation.*;
+
+/**
+ * A Rust tuple of 2 elements, written as a JSON array.
+ */
+@JsonFormat(shape = JsonFormat.Shape.ARRAY)
+@JsonPropertyOrder({{"_0", "_1"}})
+public class Tuple2<T0, T1> {{
+	private T0 _0;
+	private T1 _1;
+
+	@JsonProperty("_0")
+	public T0 get_0() {{ return _0; }}
+	@JsonProperty("_0")
+	public void set_0(T0 value) {{ this._0 = value; }}
+
+	@JsonProperty("_1")
+	public T1 get_1() {{ return _1; }}
+	@JsonProperty("_1")
+	public void set_1(T1 value) {{ this._1 = value; }}
+}}
+
+package com.example;
+
+import java.io.IOException;
+import com.fasterxml.jackson.databind.*;
+import com.fasterxml.jackson.core.JsonProcessingException;
+
+/**
+ * Serialize/Deserialize helpers
+ */
+public class Converter {{
+
+	public static Inner InnerFromJsonString(String json) throws IOException {{
+		return getInnerObjectReader().readValue(json);
+	}}
+
+	public static String InnerToJsonString(Inner obj) throws JsonProcessingException {{
+		return getInnerObjectWriter().writeValueAsString(obj);
+	}}
+
+	public static Blob BlobFromJsonString(String json) throws IOException {{
+		return getBlobObjectReader().readValue(json);
+	}}
+
+	public static String BlobToJsonString(Blob obj) throws JsonProcessingException {{
+		return getBlobObjectWriter().writeValueAsString(obj);
+	}}
+
+	public static Settings SettingsFromJsonString(String json) throws IOException {{
+		return getSettingsObjectReader().readValue(json);
+	}}
+
+	public static String SettingsToJsonString(Settings obj) throws JsonProcessingException {{
+		return getSettingsObjectWriter().writeValueAsString(obj);
+	}}
+
+	private static ObjectReader InnerReader;
+	private static ObjectWriter InnerWriter;
+
+	private static void instantiateInnerMapper() {{
+		ObjectMapper mapper = new ObjectMapper();
+		mapper.enable(DeserializationFeature.READ_UNKNOWN_ENUM_VALUES_USING_DEFAULT_VALUE);
+		mapper.findAndRegisterModules();
+		mapper.disable(SerializationFeature.WRITE_DATES_AS_TIMESTAMPS);
+		InnerReader = mapper.readerFor(Inner.class);
+		InnerWriter = mapper.writerFor(Inner.class);
+	}}
+
+	private static ObjectReader getInnerObjectReader() {{
+		if (InnerReader == null) instantiateInnerMapper();
+		return InnerReader;
+	}}
+
+	private static ObjectWriter getInnerObjectWriter() {{
+		if (InnerWriter == null) instantiateInnerMapper();
+		return InnerWriter;
+	}}
+
+	private static ObjectReader BlobReader;
+	private static ObjectWriter BlobWriter;
+
+	private static void instantiateBlobMapper() {{
+		ObjectMapper mapper = new ObjectMapper();
+		mapper.enable(DeserializationFeature.READ_UNKNOWN_ENUM_VALUES_USING_DEFAULT_VALUE);
+		mapper.findAndRegisterModules();
+		mapper.disable(SerializationFeature.WRITE_DATES_AS_TIMESTAMPS);
+		BlobReader = mapper.readerFor(Blob.class);
+		BlobWriter = mapper.writerFor(Blob.class);
+	}}
+
+	private static ObjectReader getBlobObjectReader() {{
+		if (BlobReader == null) instantiateBlobMapper();
+		return BlobReader;
+	}}
+
+	private static ObjectWriter getBlobObjectWriter() {{
+		if (BlobWriter == null) instantiateBlobMapper();
+		return BlobWriter;
+	}}
+
+	private static ObjectReader SettingsReader;
+	private static ObjectWriter SettingsWriter;
+
+	private static void instantiateSettingsMapper() {{
+		ObjectMapper mapper = new ObjectMapper();
+		mapper.enable(DeserializationFeature.READ_UNKNOWN_ENUM_VALUES_USING_DEFAULT_VALUE);
+		mapper.findAndRegisterModules();
+		mapper.disable(SerializationFeature.WRITE_DATES_AS_TIMESTAMPS);
+		SettingsReader = mapper.readerFor(Settings.class);
+		SettingsWriter = mapper.writerFor(Settings.class);
+	}}
+
+	private static ObjectReader getSettingsObjectReader() {{
+		if (SettingsReader == null) instantiateSettingsMapper();
+		return SettingsReader;
+	}}
+
+	private static ObjectWriter getSettingsObjectWriter() {{
+		if (SettingsWriter == null) instantiateSettingsMapper();
+		return SettingsWriter;
+	}}
+
+}}
+"#,
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_generate_serde_repr_enums() {
+    let mut lang = java::Java::new();
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "com.example".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[derive(Serialize_repr, Deserialize_repr)]
+#[repr(u8)]
+pub enum Small {
+    A = 1,
+    B,
+}
+
+#[derive(Serialize_repr, Deserialize_repr)]
+#[repr(u64)]
+pub enum Wide {
+    A = 1,
+    B = 4294967296,
+}
+
+#[derive(Serialize_repr, Deserialize_repr)]
+#[repr(i128)]
+pub enum Huge {
+    A = -1,
+    B = 170141183460469231731687303715884105727,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        r#"/**
+ * Generated by typeshare {}
+ */
+
+package com.example;
+
+import com.fasterxml.jackson.annotation.*;
+
+/**
+ */
+public enum Small {{
+	A(1),
+	B(2),
+	;
+
+	private final int value;
+
+	Small(int value) {{ this.value = value; }}
+
+	@JsonValue
+	public int getValue() {{ return value; }}
+}}
+
+package com.example;
+
+import com.fasterxml.jackson.annotation.*;
+
+/**
+ */
+public enum Wide {{
+	A(1L),
+	B(4294967296L),
+	;
+
+	private final long value;
+
+	Wide(long value) {{ this.value = value; }}
+
+	@JsonValue
+	public long getValue() {{ return value; }}
+}}
+
+package com.example;
+
+import com.fasterxml.jackson.annotation.*;
+
+/**
+ */
+public enum Huge {{
+	A(new java.math.BigInteger("-1")),
+	B(new java.math.BigInteger("170141183460469231731687303715884105727")),
+	;
+
+	private final java.math.BigInteger value;
+
+	Huge(java.math.BigInteger value) {{ this.value = value; }}
+
+	@JsonValue
+	public java.math.BigInteger getValue() {{ return value; }}
+}}
+
+package com.example;
+
+import java.io.IOException;
+import com.fasterxml.jackson.databind.*;
+import com.fasterxml.jackson.core.JsonProcessingException;
+
+/**
+ * Serialize/Deserialize helpers
+ */
+public class Converter {{
+
+}}
+"#,
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
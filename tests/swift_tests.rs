@@ -1,4 +1,4 @@
-use text_diff;
+use typeshare::config::Config;
 use typeshare::language::{Generator, Params};
 use typeshare::swift;
 
@@ -11,6 +11,7 @@ fn generate_types() {
             use_marker: false,
             swift_prefix: "".to_string(),
             java_package: "".to_string(),
+            ..Default::default()
         },
     );
 
@@ -76,6 +77,7 @@ fn can_generate_simple_struct_with_a_comment() {
             use_marker: false,
             swift_prefix: "".to_string(),
             java_package: "".to_string(),
+            ..Default::default()
         },
     );
 
@@ -143,6 +145,7 @@ fn can_handle_serde_rename() {
             use_marker: true,
             swift_prefix: "TypeShareX_".to_string(),
             java_package: "".to_string(),
+            ..Default::default()
         },
     );
 
@@ -204,6 +207,166 @@ public extension TypeShareX_Person {{
     assert_eq!(expected, result);
 }
 
+#[test]
+fn can_name_properties_after_non_identifier_keys() {
+    let mut lang = swift::Swift::new();
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[serde(rename_all = "kebab-case")]
+pub struct Header {
+    pub content_type: String,
+    #[serde(rename = "2fa")]
+    pub two_factor: bool,
+}
+
+#[serde(rename_all = "kebab-case")]
+pub enum Level {
+    VeryHigh,
+    Low,
+}
+
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum Mode {
+    ReadOnly,
+}
+
+#[serde(rename_all = "SCREAMING-KEBAB-CASE")]
+#[serde(deny_unknown_fields)]
+pub struct Strict {
+    pub max_size: u32,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        r#"/*
+ Generated by typeshare {}
+*/
+
+import Foundation
+
+public struct Header: Codable {{
+	public let contentType: String
+	public let _2fa: Bool
+
+	enum CodingKeys: String, CodingKey {{
+		case contentType = "content-type", _2fa = "2fa"
+	}}
+
+	public init(contentType: String, _2fa: Bool) {{
+		self.contentType = contentType
+		self._2fa = _2fa
+	}}
+}}
+
+
+public extension Header {{
+	init(data: Data) throws {{
+		let decoded = try JSONDecoder().decode(Header.self, from: data)
+		self.init(contentType: decoded.contentType, _2fa: decoded._2fa)
+	}}
+}}
+
+public struct Strict: Codable {{
+	public let maxSize: UInt32
+
+	enum CodingKeys: String, CodingKey {{
+		case maxSize = "MAX-SIZE"
+	}}
+
+	public init(from decoder: Decoder) throws {{
+		let knownKeys: Set<String> = ["MAX-SIZE"]
+		if let unknownKey = try decoder.container(keyedBy: AnyCodingKey.self).allKeys.first(where: {{ !knownKeys.contains($0.stringValue) }}) {{
+			throw DecodingError.dataCorrupted(DecodingError.Context(codingPath: decoder.codingPath + [unknownKey], debugDescription: "Unknown field `\(unknownKey.stringValue)`"))
+		}}
+		let container = try decoder.container(keyedBy: CodingKeys.self)
+		self.maxSize = try container.decode(UInt32.self, forKey: .maxSize)
+	}}
+
+	public init(maxSize: UInt32) {{
+		self.maxSize = maxSize
+	}}
+}}
+
+
+public extension Strict {{
+	init(data: Data) throws {{
+		let decoded = try JSONDecoder().decode(Strict.self, from: data)
+		self.init(maxSize: decoded.maxSize)
+	}}
+}}
+
+public enum Level: String, Codable {{
+	case veryHigh = "very-high"
+	case low = "low"
+}}
+
+public enum Mode: RawRepresentable, Codable, Hashable {{
+	case readOnly
+	/// A case added after this type was generated.
+	case unknown(String)
+
+	public init(rawValue: String) {{
+		switch rawValue {{
+		case "read-only": self = .readOnly
+		default: self = .unknown(rawValue)
+		}}
+	}}
+
+	public var rawValue: String {{
+		switch self {{
+		case .readOnly: return "read-only"
+		case .unknown(let value): return value
+		}}
+	}}
+
+	public init(from decoder: Decoder) throws {{
+		self.init(rawValue: try decoder.singleValueContainer().decode(String.self))
+	}}
+
+	public func encode(to encoder: Encoder) throws {{
+		var container = encoder.singleValueContainer()
+		try container.encode(rawValue)
+	}}
+}}
+
+/// Coding key for object keys that are only known at runtime.
+public struct AnyCodingKey: CodingKey {{
+	public var stringValue: String
+	public var intValue: Int? {{ return nil }}
+
+	public init?(stringValue: String) {{
+		self.stringValue = stringValue
+	}}
+
+	public init?(intValue: Int) {{
+		return nil
+	}}
+}}
+
+"#,
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
 #[test]
 fn can_generate_simple_enum() {
     let mut lang = swift::Swift::new();
@@ -213,6 +376,7 @@ fn can_generate_simple_enum() {
             use_marker: false,
             swift_prefix: "TypeShare".to_string(),
             java_package: "".to_string(),
+            ..Default::default()
         },
     );
 
@@ -263,6 +427,7 @@ fn can_generate_bare_string_enum() {
             use_marker: false,
             swift_prefix: "".to_string(),
             java_package: "".to_string(),
+            ..Default::default()
         },
     );
 
@@ -303,6 +468,188 @@ public enum Colors: String, Codable {{
     assert_eq!(expected, result);
 }
 
+#[test]
+fn can_apply_enum_tagging_to_struct_variants() {
+    let mut lang = swift::Swift::new();
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[typeshare]
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Shape {
+    Circle { radius: f64 },
+    Rect { width: f64, height: f64 },
+}
+
+#[typeshare]
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "t", content = "c")]
+pub enum Event {
+    Moved { x: i32, y: i32 },
+    Renamed(String),
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        r#"/*
+ Generated by typeshare {}
+*/
+
+import Foundation
+
+public struct ShapeCircle: Codable {{
+	public let radius: Double
+
+	public init(radius: Double) {{
+		self.radius = radius
+	}}
+}}
+
+
+public extension ShapeCircle {{
+	init(data: Data) throws {{
+		let decoded = try JSONDecoder().decode(ShapeCircle.self, from: data)
+		self.init(radius: decoded.radius)
+	}}
+}}
+
+public struct ShapeRect: Codable {{
+	public let width: Double
+	public let height: Double
+
+	public init(width: Double, height: Double) {{
+		self.width = width
+		self.height = height
+	}}
+}}
+
+
+public extension ShapeRect {{
+	init(data: Data) throws {{
+		let decoded = try JSONDecoder().decode(ShapeRect.self, from: data)
+		self.init(width: decoded.width, height: decoded.height)
+	}}
+}}
+
+public struct EventMoved: Codable {{
+	public let x: Int32
+	public let y: Int32
+
+	public init(x: Int32, y: Int32) {{
+		self.x = x
+		self.y = y
+	}}
+}}
+
+
+public extension EventMoved {{
+	init(data: Data) throws {{
+		let decoded = try JSONDecoder().decode(EventMoved.self, from: data)
+		self.init(x: decoded.x, y: decoded.y)
+	}}
+}}
+
+public enum Shape: Codable {{
+	case circle(ShapeCircle)
+	case rect(ShapeRect)
+
+	public init(from decoder: Decoder) throws {{
+		let container = try decoder.container(keyedBy: AnyCodingKey.self)
+		switch try container.decode(String.self, forKey: AnyCodingKey(stringValue: "type")!) {{
+		case "circle":
+			self = .circle(try ShapeCircle(from: decoder))
+			return
+		case "rect":
+			self = .rect(try ShapeRect(from: decoder))
+			return
+		default:
+			break
+		}}
+		throw DecodingError.typeMismatch(Shape.self, DecodingError.Context(codingPath: decoder.codingPath, debugDescription: "Wrong type for Shape"))
+	}}
+
+	public func encode(to encoder: Encoder) throws {{
+		var container = encoder.container(keyedBy: AnyCodingKey.self)
+		switch self {{
+		case .circle(let x):
+			try container.encode("circle", forKey: AnyCodingKey(stringValue: "type")!)
+			try x.encode(to: encoder)
+		case .rect(let x):
+			try container.encode("rect", forKey: AnyCodingKey(stringValue: "type")!)
+			try x.encode(to: encoder)
+		}}
+	}}
+}}
+
+public enum Event: Codable {{
+	case Moved(EventMoved)
+	case Renamed(String)
+
+	public init(from decoder: Decoder) throws {{
+		let container = try decoder.container(keyedBy: AnyCodingKey.self)
+		switch try container.decode(String.self, forKey: AnyCodingKey(stringValue: "t")!) {{
+		case "Moved":
+			self = .Moved(try container.decode(EventMoved.self, forKey: AnyCodingKey(stringValue: "c")!))
+			return
+		case "Renamed":
+			self = .Renamed(try container.decode(String.self, forKey: AnyCodingKey(stringValue: "c")!))
+			return
+		default:
+			break
+		}}
+		throw DecodingError.typeMismatch(Event.self, DecodingError.Context(codingPath: decoder.codingPath, debugDescription: "Wrong type for Event"))
+	}}
+
+	public func encode(to encoder: Encoder) throws {{
+		var container = encoder.container(keyedBy: AnyCodingKey.self)
+		switch self {{
+		case .Moved(let x):
+			try container.encode("Moved", forKey: AnyCodingKey(stringValue: "t")!)
+			try container.encode(x, forKey: AnyCodingKey(stringValue: "c")!)
+		case .Renamed(let x):
+			try container.encode("Renamed", forKey: AnyCodingKey(stringValue: "t")!)
+			try container.encode(x, forKey: AnyCodingKey(stringValue: "c")!)
+		}}
+	}}
+}}
+
+/// Coding key for object keys that are only known at runtime.
+public struct AnyCodingKey: CodingKey {{
+	public var stringValue: String
+	public var intValue: Int? {{ return nil }}
+
+	public init?(stringValue: String) {{
+		self.stringValue = stringValue
+	}}
+
+	public init?(intValue: Int) {{
+		return nil
+	}}
+}}
+
+"#,
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
 #[test]
 fn can_generate_algebraic_enum() {
     let mut lang = swift::Swift::new();
@@ -312,6 +659,7 @@ fn can_generate_algebraic_enum() {
             use_marker: false,
             swift_prefix: "OP".to_string(),
             java_package: "".to_string(),
+            ..Default::default()
         },
     );
 
@@ -341,41 +689,3651 @@ public enum OPAdvancedColors: Codable {{
 	case reallyCoolType(ItemDetailsFieldValue)
 
 	public init(from decoder: Decoder) throws {{
-		let container = try decoder.singleValueContainer()
-		if let x = try? container.decode(String.self) {{
-			self = .string(x)
+		let container = try decoder.container(keyedBy: OPAnyCodingKey.self)
+		if container.contains(OPAnyCodingKey(stringValue: "string")!) {{
+			self = .string(try container.decode(String.self, forKey: OPAnyCodingKey(stringValue: "string")!))
 			return
 		}}
-		if let x = try? container.decode(Int32.self) {{
-			self = .number(x)
+		if container.contains(OPAnyCodingKey(stringValue: "number")!) {{
+			self = .number(try container.decode(Int32.self, forKey: OPAnyCodingKey(stringValue: "number")!))
 			return
 		}}
-		if let x = try? container.decode([Int32].self) {{
-			self = .numberArray(x)
+		if container.contains(OPAnyCodingKey(stringValue: "numberArray")!) {{
+			self = .numberArray(try container.decode([Int32].self, forKey: OPAnyCodingKey(stringValue: "numberArray")!))
 			return
 		}}
-		if let x = try? container.decode(ItemDetailsFieldValue.self) {{
-			self = .reallyCoolType(x)
+		if container.contains(OPAnyCodingKey(stringValue: "reallyCoolType")!) {{
+			self = .reallyCoolType(try container.decode(ItemDetailsFieldValue.self, forKey: OPAnyCodingKey(stringValue: "reallyCoolType")!))
 			return
 		}}
 		throw DecodingError.typeMismatch(OPAdvancedColors.self, DecodingError.Context(codingPath: decoder.codingPath, debugDescription: "Wrong type for OPAdvancedColors"))
 	}}
 
 	public func encode(to encoder: Encoder) throws {{
-		var container = encoder.singleValueContainer()
+		var container = encoder.container(keyedBy: OPAnyCodingKey.self)
 		switch self {{
 		case .string(let x):
-			try container.encode(x)
+			try container.encode(x, forKey: OPAnyCodingKey(stringValue: "string")!)
 		case .number(let x):
-			try container.encode(x)
+			try container.encode(x, forKey: OPAnyCodingKey(stringValue: "number")!)
 		case .numberArray(let x):
-			try container.encode(x)
+			try container.encode(x, forKey: OPAnyCodingKey(stringValue: "numberArray")!)
 		case .reallyCoolType(let x):
-			try container.encode(x)
+			try container.encode(x, forKey: OPAnyCodingKey(stringValue: "reallyCoolType")!)
 		}}
 	}}
 }}
 
+/// Coding key for object keys that are only known at runtime.
+public struct OPAnyCodingKey: CodingKey {{
+	public var stringValue: String
+	public var intValue: Int? {{ return nil }}
+
+	public init?(stringValue: String) {{
+		self.stringValue = stringValue
+	}}
+
+	public init?(intValue: Int) {{
+		return nil
+	}}
+}}
+
+"#,
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_decode_missing_default_fields() {
+    let mut lang = swift::Swift::new();
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+pub struct Settings {
+    pub name: String,
+    pub nickname: Option<String>,
+    #[serde(default)]
+    pub retries: u32,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub theme: Theme,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        r#"/*
+ Generated by typeshare {}
+*/
+
+import Foundation
+
+public struct Settings: Codable {{
+	public let name: String
+	public let nickname: String?
+	public let retries: UInt32
+	public let tags: [String]
+	public let theme: Theme?
+
+	enum CodingKeys: String, CodingKey {{
+		case name, nickname, retries, tags, theme
+	}}
+
+	public init(from decoder: Decoder) throws {{
+		let container = try decoder.container(keyedBy: CodingKeys.self)
+		self.name = try container.decode(String.self, forKey: .name)
+		self.nickname = try container.decodeIfPresent(String.self, forKey: .nickname)
+		self.retries = try container.decodeIfPresent(UInt32.self, forKey: .retries) ?? 0
+		self.tags = try container.decodeIfPresent([String].self, forKey: .tags) ?? []
+		self.theme = try container.decodeIfPresent(Theme.self, forKey: .theme)
+	}}
+
+	public init(name: String, nickname: String?, retries: UInt32, tags: [String], theme: Theme?) {{
+		self.name = name
+		self.nickname = nickname
+		self.retries = retries
+		self.tags = tags
+		self.theme = theme
+	}}
+}}
+
+
+public extension Settings {{
+	init(data: Data) throws {{
+		let decoded = try JSONDecoder().decode(Settings.self, from: data)
+		self.init(name: decoded.name, nickname: decoded.nickname, retries: decoded.retries, tags: decoded.tags, theme: decoded.theme)
+	}}
+}}
+
+"#,
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_handle_serde_flatten() {
+    let mut lang = swift::Swift::new();
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+pub struct Vault {
+    pub name: String,
+    #[serde(flatten)]
+    pub common: CommonFields,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        r#"/*
+ Generated by typeshare {}
+*/
+
+import Foundation
+
+public struct Vault: Codable {{
+	public let name: String
+	public let common: CommonFields
+
+	enum CodingKeys: String, CodingKey {{
+		case name
+	}}
+
+	public init(from decoder: Decoder) throws {{
+		let container = try decoder.container(keyedBy: CodingKeys.self)
+		self.name = try container.decode(String.self, forKey: .name)
+		self.common = try CommonFields(from: decoder)
+	}}
+
+	public func encode(to encoder: Encoder) throws {{
+		var container = encoder.container(keyedBy: CodingKeys.self)
+		try container.encode(name, forKey: .name)
+		try common.encode(to: encoder)
+	}}
+
+	public init(name: String, common: CommonFields) {{
+		self.name = name
+		self.common = common
+	}}
+}}
+
+
+public extension Vault {{
+	init(data: Data) throws {{
+		let decoded = try JSONDecoder().decode(Vault.self, from: data)
+		self.init(name: decoded.name, common: decoded.common)
+	}}
+}}
+
+"#,
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_leave_keys_of_flattened_structs_out_of_catch_all_map() {
+    let mut lang = swift::Swift::new();
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[typeshare]
+pub struct Paging {
+    pub page: u32,
+    #[serde(rename = "per_page", alias = "perPage")]
+    pub per_page: u32,
+}
+
+#[typeshare]
+pub struct Query {
+    pub search: String,
+    #[serde(flatten)]
+    pub paging: Paging,
+    #[serde(flatten)]
+    pub extra: HashMap<String, String>,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        r#"/*
+ Generated by typeshare {}
+*/
+
+import Foundation
+
+public struct Paging: Codable {{
+	public let page: UInt32
+	public let per_page: UInt32
+
+	enum CodingKeys: String, CodingKey {{
+		case page, per_page
+	}}
+
+	public init(from decoder: Decoder) throws {{
+		let container = try decoder.container(keyedBy: CodingKeys.self)
+		let aliasContainer = try decoder.container(keyedBy: AnyCodingKey.self)
+		self.page = try container.decode(UInt32.self, forKey: .page)
+		self.per_page = try aliasContainer.decode(UInt32.self, forFirstOf: ["per_page", "perPage"])
+	}}
+
+	public init(page: UInt32, per_page: UInt32) {{
+		self.page = page
+		self.per_page = per_page
+	}}
+}}
+
+
+public extension Paging {{
+	init(data: Data) throws {{
+		let decoded = try JSONDecoder().decode(Paging.self, from: data)
+		self.init(page: decoded.page, per_page: decoded.per_page)
+	}}
+}}
+
+public struct Query: Codable {{
+	public let search: String
+	public let paging: Paging
+	public let extra: [String: String]
+
+	enum CodingKeys: String, CodingKey {{
+		case search
+	}}
+
+	public init(from decoder: Decoder) throws {{
+		let container = try decoder.container(keyedBy: CodingKeys.self)
+		self.search = try container.decode(String.self, forKey: .search)
+		self.paging = try Paging(from: decoder)
+		let extraContainer = try decoder.container(keyedBy: AnyCodingKey.self)
+		var extra = [String: String]()
+		let flattenedKeys: Set<String> = ["page", "per_page", "perPage"]
+		for key in extraContainer.allKeys where CodingKeys(stringValue: key.stringValue) == nil && !flattenedKeys.contains(key.stringValue) {{
+			extra[key.stringValue] = try extraContainer.decode(String.self, forKey: key)
+		}}
+		self.extra = extra
+	}}
+
+	public func encode(to encoder: Encoder) throws {{
+		var container = encoder.container(keyedBy: CodingKeys.self)
+		try container.encode(search, forKey: .search)
+		try paging.encode(to: encoder)
+		var extraContainer = encoder.container(keyedBy: AnyCodingKey.self)
+		for (key, value) in extra {{
+			try extraContainer.encode(value, forKey: AnyCodingKey(stringValue: key)!)
+		}}
+	}}
+
+	public init(search: String, paging: Paging, extra: [String: String]) {{
+		self.search = search
+		self.paging = paging
+		self.extra = extra
+	}}
+}}
+
+
+public extension Query {{
+	init(data: Data) throws {{
+		let decoded = try JSONDecoder().decode(Query.self, from: data)
+		self.init(search: decoded.search, paging: decoded.paging, extra: decoded.extra)
+	}}
+}}
+
+/// Coding key for object keys that are only known at runtime.
+public struct AnyCodingKey: CodingKey {{
+	public var stringValue: String
+	public var intValue: Int? {{ return nil }}
+
+	public init?(stringValue: String) {{
+		self.stringValue = stringValue
+	}}
+
+	public init?(intValue: Int) {{
+		return nil
+	}}
+}}
+
+extension KeyedDecodingContainer where Key == AnyCodingKey {{
+	/// Whether any of `keys` is present.
+	func contains(anyOf keys: [String]) -> Bool {{
+		return keys.compactMap(Key.init(stringValue:)).contains(where: contains)
+	}}
+
+	/// Decodes the value stored under the first of `keys` that is present.
+	func decode<T: Decodable>(_ type: T.Type, forFirstOf keys: [String]) throws -> T {{
+		let key = keys.compactMap(Key.init(stringValue:)).first(where: contains) ?? Key(stringValue: keys[0])!
+		return try decode(type, forKey: key)
+	}}
+
+	/// Decodes the value stored under the first of `keys` that is present, or `nil` if there is none.
+	func decodeIfPresent<T: Decodable>(_ type: T.Type, forFirstOf keys: [String]) throws -> T? {{
+		guard let key = keys.compactMap(Key.init(stringValue:)).first(where: contains) else {{
+			return nil
+		}}
+		return try decodeIfPresent(type, forKey: key)
+	}}
+}}
+
+"#,
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_handle_flattened_catch_all_map() {
+    let mut lang = swift::Swift::new();
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "OP".to_string(),
+            java_package: "".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+pub struct Item {
+    pub title: String,
+    #[serde(flatten)]
+    pub extra: HashMap<String, String>,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        r#"/*
+ Generated by typeshare {}
+*/
+
+import Foundation
+
+public struct OPItem: Codable {{
+	public let title: String
+	public let extra: [String: String]
+
+	enum CodingKeys: String, CodingKey {{
+		case title
+	}}
+
+	public init(from decoder: Decoder) throws {{
+		let container = try decoder.container(keyedBy: CodingKeys.self)
+		self.title = try container.decode(String.self, forKey: .title)
+		let extraContainer = try decoder.container(keyedBy: OPAnyCodingKey.self)
+		var extra = [String: String]()
+		for key in extraContainer.allKeys where CodingKeys(stringValue: key.stringValue) == nil {{
+			extra[key.stringValue] = try extraContainer.decode(String.self, forKey: key)
+		}}
+		self.extra = extra
+	}}
+
+	public func encode(to encoder: Encoder) throws {{
+		var container = encoder.container(keyedBy: CodingKeys.self)
+		try container.encode(title, forKey: .title)
+		var extraContainer = encoder.container(keyedBy: OPAnyCodingKey.self)
+		for (key, value) in extra {{
+			try extraContainer.encode(value, forKey: OPAnyCodingKey(stringValue: key)!)
+		}}
+	}}
+
+	public init(title: String, extra: [String: String]) {{
+		self.title = title
+		self.extra = extra
+	}}
+}}
+
+
+public extension OPItem {{
+	init(data: Data) throws {{
+		let decoded = try JSONDecoder().decode(OPItem.self, from: data)
+		self.init(title: decoded.title, extra: decoded.extra)
+	}}
+}}
+
+/// Coding key for object keys that are only known at runtime.
+public struct OPAnyCodingKey: CodingKey {{
+	public var stringValue: String
+	public var intValue: Int? {{ return nil }}
+
+	public init?(stringValue: String) {{
+		self.stringValue = stringValue
+	}}
+
+	public init?(intValue: Int) {{
+		return nil
+	}}
+}}
+
+"#,
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_handle_double_option() {
+    let mut lang = swift::Swift::new();
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+pub struct ItemPatch {
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "::serde_with::rust::double_option")]
+    pub url: Option<Option<String>>,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        r#"/*
+ Generated by typeshare {}
+*/
+
+import Foundation
+
+public struct ItemPatch: Codable {{
+	public let title: String?
+	public let url: DoubleOption<String>
+
+	enum CodingKeys: String, CodingKey {{
+		case title, url
+	}}
+
+	public init(from decoder: Decoder) throws {{
+		let container = try decoder.container(keyedBy: CodingKeys.self)
+		self.title = try container.decodeIfPresent(String.self, forKey: .title)
+		self.url = container.contains(.url) ? try container.decode(DoubleOption<String>.self, forKey: .url) : .absent
+	}}
+
+	public func encode(to encoder: Encoder) throws {{
+		var container = encoder.container(keyedBy: CodingKeys.self)
+		try container.encodeIfPresent(title, forKey: .title)
+		if !url.isAbsent {{
+			try container.encode(url, forKey: .url)
+		}}
+	}}
+
+	public init(title: String?, url: DoubleOption<String>) {{
+		self.title = title
+		self.url = url
+	}}
+}}
+
+
+public extension ItemPatch {{
+	init(data: Data) throws {{
+		let decoded = try JSONDecoder().decode(ItemPatch.self, from: data)
+		self.init(title: decoded.title, url: decoded.url)
+	}}
+}}
+
+/// A value that is missing, explicitly `null`, or present, like Rust's `Option<Option<T>>`.
+public enum DoubleOption<Wrapped: Codable>: Codable {{
+	case absent
+	case null
+	case value(Wrapped)
+
+	public var isAbsent: Bool {{
+		if case .absent = self {{
+			return true
+		}}
+		return false
+	}}
+
+	public func map<T: Codable>(_ transform: (Wrapped) throws -> T) rethrows -> DoubleOption<T> {{
+		switch self {{
+		case .absent:
+			return .absent
+		case .null:
+			return .null
+		case .value(let x):
+			return .value(try transform(x))
+		}}
+	}}
+
+	public init(from decoder: Decoder) throws {{
+		let container = try decoder.singleValueContainer()
+		if container.decodeNil() {{
+			self = .null
+		}} else {{
+			self = .value(try container.decode(Wrapped.self))
+		}}
+	}}
+
+	public func encode(to encoder: Encoder) throws {{
+		var container = encoder.singleValueContainer()
+		switch self {{
+		case .absent, .null:
+			try container.encodeNil()
+		case .value(let x):
+			try container.encode(x)
+		}}
+	}}
+}}
+
+"#,
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_handle_double_option_byte_arrays() {
+    let mut lang = swift::Swift::new();
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[typeshare]
+#[derive(Serialize, Deserialize)]
+pub struct Avatar {
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "::serde_with::rust::double_option")]
+    pub image: Option<Option<Vec<u8>>>,
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "::serde_with::rust::double_option")]
+    pub caption: Option<Option<String>>,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        "/*
+ Generated by typeshare {}
+*/
+
+import Foundation
+
+public struct Avatar: Codable {{
+	public let image: DoubleOption<Data>
+	public let caption: DoubleOption<String>
+
+	enum CodingKeys: String, CodingKey {{
+		case image, caption
+	}}
+
+	public init(from decoder: Decoder) throws {{
+		let container = try decoder.container(keyedBy: CodingKeys.self)
+		self.image = container.contains(.image) ? try container.decode(DoubleOption<[UInt8]>.self, forKey: .image).map {{ Data($0) }} : .absent
+		self.caption = container.contains(.caption) ? try container.decode(DoubleOption<String>.self, forKey: .caption) : .absent
+	}}
+
+	public func encode(to encoder: Encoder) throws {{
+		var container = encoder.container(keyedBy: CodingKeys.self)
+		if !image.isAbsent {{
+			try container.encode(image.map {{ [UInt8]($0) }}, forKey: .image)
+		}}
+		if !caption.isAbsent {{
+			try container.encode(caption, forKey: .caption)
+		}}
+	}}
+
+	public init(image: DoubleOption<Data>, caption: DoubleOption<String>) {{
+		self.image = image
+		self.caption = caption
+	}}
+}}
+
+
+public extension Avatar {{
+	init(data: Data) throws {{
+		let decoded = try JSONDecoder().decode(Avatar.self, from: data)
+		self.init(image: decoded.image, caption: decoded.caption)
+	}}
+}}
+
+/// A value that is missing, explicitly `null`, or present, like Rust's `Option<Option<T>>`.
+public enum DoubleOption<Wrapped: Codable>: Codable {{
+	case absent
+	case null
+	case value(Wrapped)
+
+	public var isAbsent: Bool {{
+		if case .absent = self {{
+			return true
+		}}
+		return false
+	}}
+
+	public func map<T: Codable>(_ transform: (Wrapped) throws -> T) rethrows -> DoubleOption<T> {{
+		switch self {{
+		case .absent:
+			return .absent
+		case .null:
+			return .null
+		case .value(let x):
+			return .value(try transform(x))
+		}}
+	}}
+
+	public init(from decoder: Decoder) throws {{
+		let container = try decoder.singleValueContainer()
+		if container.decodeNil() {{
+			self = .null
+		}} else {{
+			self = .value(try container.decode(Wrapped.self))
+		}}
+	}}
+
+	public func encode(to encoder: Encoder) throws {{
+		var container = encoder.singleValueContainer()
+		switch self {{
+		case .absent, .null:
+			try container.encodeNil()
+		case .value(let x):
+			try container.encode(x)
+		}}
+	}}
+}}
+
+",
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_handle_per_direction_renames_and_aliases() {
+    let mut lang = swift::Swift::new();
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+pub struct Item {
+    #[serde(rename(serialize = "itemTitle", deserialize = "title"))]
+    pub item_title: String,
+    #[serde(alias = "desc", alias = "description")]
+    pub notes: Option<String>,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        r#"/*
+ Generated by typeshare {}
+*/
+
+import Foundation
+
+public struct Item: Codable {{
+	public let itemTitle: String
+	public let notes: String?
+
+	enum CodingKeys: String, CodingKey {{
+		case itemTitle, notes
+	}}
+
+	public init(from decoder: Decoder) throws {{
+		let container = try decoder.container(keyedBy: CodingKeys.self)
+		let aliasContainer = try decoder.container(keyedBy: AnyCodingKey.self)
+		self.itemTitle = try container.decode(String.self, forKey: .itemTitle)
+		self.notes = try aliasContainer.decodeIfPresent(String.self, forFirstOf: ["notes", "desc", "description"])
+	}}
+
+	public func encode(to encoder: Encoder) throws {{
+		var container = encoder.container(keyedBy: CodingKeys.self)
+		var renamedContainer = encoder.container(keyedBy: AnyCodingKey.self)
+		try renamedContainer.encode(itemTitle, forKey: AnyCodingKey(stringValue: "title")!)
+		try container.encodeIfPresent(notes, forKey: .notes)
+	}}
+
+	public init(itemTitle: String, notes: String?) {{
+		self.itemTitle = itemTitle
+		self.notes = notes
+	}}
+}}
+
+
+public extension Item {{
+	init(data: Data) throws {{
+		let decoded = try JSONDecoder().decode(Item.self, from: data)
+		self.init(itemTitle: decoded.itemTitle, notes: decoded.notes)
+	}}
+}}
+
+/// Coding key for object keys that are only known at runtime.
+public struct AnyCodingKey: CodingKey {{
+	public var stringValue: String
+	public var intValue: Int? {{ return nil }}
+
+	public init?(stringValue: String) {{
+		self.stringValue = stringValue
+	}}
+
+	public init?(intValue: Int) {{
+		return nil
+	}}
+}}
+
+extension KeyedDecodingContainer where Key == AnyCodingKey {{
+	/// Whether any of `keys` is present.
+	func contains(anyOf keys: [String]) -> Bool {{
+		return keys.compactMap(Key.init(stringValue:)).contains(where: contains)
+	}}
+
+	/// Decodes the value stored under the first of `keys` that is present.
+	func decode<T: Decodable>(_ type: T.Type, forFirstOf keys: [String]) throws -> T {{
+		let key = keys.compactMap(Key.init(stringValue:)).first(where: contains) ?? Key(stringValue: keys[0])!
+		return try decode(type, forKey: key)
+	}}
+
+	/// Decodes the value stored under the first of `keys` that is present, or `nil` if there is none.
+	func decodeIfPresent<T: Decodable>(_ type: T.Type, forFirstOf keys: [String]) throws -> T? {{
+		guard let key = keys.compactMap(Key.init(stringValue:)).first(where: contains) else {{
+			return nil
+		}}
+		return try decodeIfPresent(type, forKey: key)
+	}}
+}}
+
+"#,
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_decode_aliased_double_options() {
+    let mut lang = swift::Swift::new();
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+pub struct Only {
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "::serde_with::rust::double_option")]
+    #[serde(alias = "x")]
+    pub a: Option<Option<String>>,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        r#"/*
+ Generated by typeshare {}
+*/
+
+import Foundation
+
+public struct Only: Codable {{
+	public let a: DoubleOption<String>
+
+	enum CodingKeys: String, CodingKey {{
+		case a
+	}}
+
+	public init(from decoder: Decoder) throws {{
+		let aliasContainer = try decoder.container(keyedBy: AnyCodingKey.self)
+		self.a = aliasContainer.contains(anyOf: ["a", "x"]) ? try aliasContainer.decode(DoubleOption<String>.self, forFirstOf: ["a", "x"]) : .absent
+	}}
+
+	public func encode(to encoder: Encoder) throws {{
+		var container = encoder.container(keyedBy: CodingKeys.self)
+		if !a.isAbsent {{
+			try container.encode(a, forKey: .a)
+		}}
+	}}
+
+	public init(a: DoubleOption<String>) {{
+		self.a = a
+	}}
+}}
+
+
+public extension Only {{
+	init(data: Data) throws {{
+		let decoded = try JSONDecoder().decode(Only.self, from: data)
+		self.init(a: decoded.a)
+	}}
+}}
+
+/// Coding key for object keys that are only known at runtime.
+public struct AnyCodingKey: CodingKey {{
+	public var stringValue: String
+	public var intValue: Int? {{ return nil }}
+
+	public init?(stringValue: String) {{
+		self.stringValue = stringValue
+	}}
+
+	public init?(intValue: Int) {{
+		return nil
+	}}
+}}
+
+extension KeyedDecodingContainer where Key == AnyCodingKey {{
+	/// Whether any of `keys` is present.
+	func contains(anyOf keys: [String]) -> Bool {{
+		return keys.compactMap(Key.init(stringValue:)).contains(where: contains)
+	}}
+
+	/// Decodes the value stored under the first of `keys` that is present.
+	func decode<T: Decodable>(_ type: T.Type, forFirstOf keys: [String]) throws -> T {{
+		let key = keys.compactMap(Key.init(stringValue:)).first(where: contains) ?? Key(stringValue: keys[0])!
+		return try decode(type, forKey: key)
+	}}
+
+	/// Decodes the value stored under the first of `keys` that is present, or `nil` if there is none.
+	func decodeIfPresent<T: Decodable>(_ type: T.Type, forFirstOf keys: [String]) throws -> T? {{
+		guard let key = keys.compactMap(Key.init(stringValue:)).first(where: contains) else {{
+			return nil
+		}}
+		return try decodeIfPresent(type, forKey: key)
+	}}
+}}
+
+/// A value that is missing, explicitly `null`, or present, like Rust's `Option<Option<T>>`.
+public enum DoubleOption<Wrapped: Codable>: Codable {{
+	case absent
+	case null
+	case value(Wrapped)
+
+	public var isAbsent: Bool {{
+		if case .absent = self {{
+			return true
+		}}
+		return false
+	}}
+
+	public func map<T: Codable>(_ transform: (Wrapped) throws -> T) rethrows -> DoubleOption<T> {{
+		switch self {{
+		case .absent:
+			return .absent
+		case .null:
+			return .null
+		case .value(let x):
+			return .value(try transform(x))
+		}}
+	}}
+
+	public init(from decoder: Decoder) throws {{
+		let container = try decoder.singleValueContainer()
+		if container.decodeNil() {{
+			self = .null
+		}} else {{
+			self = .value(try container.decode(Wrapped.self))
+		}}
+	}}
+
+	public func encode(to encoder: Encoder) throws {{
+		var container = encoder.singleValueContainer()
+		switch self {{
+		case .absent, .null:
+			try container.encodeNil()
+		case .value(let x):
+			try container.encode(x)
+		}}
+	}}
+}}
+
+"#,
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_handle_serde_transparent() {
+    let mut lang = swift::Swift::new();
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            swift_wrap_transparent: true,
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+/// Identifies a vault
+#[serde(transparent)]
+pub struct VaultId(String);
+
+#[serde(transparent)]
+pub struct Tags {
+    pub tags: Vec<String>,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        r#"/*
+ Generated by typeshare {}
+*/
+
+import Foundation
+
+/// Identifies a vault
+public struct VaultId: Codable {{
+	public let value: String
+
+	public init(_ value: String) {{
+		self.value = value
+	}}
+
+	public init(from decoder: Decoder) throws {{
+		let container = try decoder.singleValueContainer()
+		self.value = try container.decode(String.self)
+	}}
+
+	public func encode(to encoder: Encoder) throws {{
+		var container = encoder.singleValueContainer()
+		try container.encode(value)
+	}}
+}}
+
+public struct Tags: Codable {{
+	public let value: [String]
+
+	public init(_ value: [String]) {{
+		self.value = value
+	}}
+
+	public init(from decoder: Decoder) throws {{
+		let container = try decoder.singleValueContainer()
+		self.value = try container.decode([String].self)
+	}}
+
+	public func encode(to encoder: Encoder) throws {{
+		var container = encoder.singleValueContainer()
+		try container.encode(value)
+	}}
+}}
+
+"#,
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_type_transparent_byte_arrays() {
+    let mut lang = swift::Swift::new();
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[serde(transparent)]
+pub struct Blob(Vec<u8>);
+
+#[serde(transparent)]
+pub struct Key(#[serde(with = "base64")] Vec<u8>);
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        "/*
+ Generated by typeshare {}
+*/
+
+import Foundation
+
+public typealias Blob = [UInt8]
+
+public typealias Key = Data
+
+",
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_wrap_transparent_byte_arrays() {
+    let mut lang = swift::Swift::new();
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            swift_wrap_transparent: true,
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[serde(transparent)]
+pub struct Blob(Vec<u8>);
+
+#[serde(transparent)]
+pub struct Key(#[serde(with = "base64")] Vec<u8>);
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        "/*
+ Generated by typeshare {}
+*/
+
+import Foundation
+
+public struct Blob: Codable {{
+	public let value: [UInt8]
+
+	public init(_ value: [UInt8]) {{
+		self.value = value
+	}}
+
+	public init(from decoder: Decoder) throws {{
+		let container = try decoder.singleValueContainer()
+		self.value = try container.decode([UInt8].self)
+	}}
+
+	public func encode(to encoder: Encoder) throws {{
+		var container = encoder.singleValueContainer()
+		try container.encode(value)
+	}}
+}}
+
+public struct Key: Codable {{
+	public let value: Data
+
+	public init(_ value: Data) {{
+		self.value = value
+	}}
+
+	public init(from decoder: Decoder) throws {{
+		let container = try decoder.singleValueContainer()
+		self.value = try container.decode(Data.self)
+	}}
+
+	public func encode(to encoder: Encoder) throws {{
+		var container = encoder.singleValueContainer()
+		try container.encode(value)
+	}}
+}}
+
+",
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_reject_unknown_fields() {
+    let mut lang = swift::Swift::new();
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[serde(deny_unknown_fields)]
+pub struct Login {
+    pub username: String,
+    pub password: String,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        r#"/*
+ Generated by typeshare {}
+*/
+
+import Foundation
+
+public struct Login: Codable {{
+	public let username: String
+	public let password: String
+
+	enum CodingKeys: String, CodingKey {{
+		case username, password
+	}}
+
+	public init(from decoder: Decoder) throws {{
+		let knownKeys: Set<String> = ["username", "password"]
+		if let unknownKey = try decoder.container(keyedBy: AnyCodingKey.self).allKeys.first(where: {{ !knownKeys.contains($0.stringValue) }}) {{
+			throw DecodingError.dataCorrupted(DecodingError.Context(codingPath: decoder.codingPath + [unknownKey], debugDescription: "Unknown field `\(unknownKey.stringValue)`"))
+		}}
+		let container = try decoder.container(keyedBy: CodingKeys.self)
+		self.username = try container.decode(String.self, forKey: .username)
+		self.password = try container.decode(String.self, forKey: .password)
+	}}
+
+	public init(username: String, password: String) {{
+		self.username = username
+		self.password = password
+	}}
+}}
+
+
+public extension Login {{
+	init(data: Data) throws {{
+		let decoded = try JSONDecoder().decode(Login.self, from: data)
+		self.init(username: decoded.username, password: decoded.password)
+	}}
+}}
+
+/// Coding key for object keys that are only known at runtime.
+public struct AnyCodingKey: CodingKey {{
+	public var stringValue: String
+	public var intValue: Int? {{ return nil }}
+
+	public init?(stringValue: String) {{
+		self.stringValue = stringValue
+	}}
+
+	public init?(intValue: Int) {{
+		return nil
+	}}
+}}
+
+"#,
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_decode_unknown_enum_cases() {
+    let mut lang = swift::Swift::new();
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+/// Where an item came from
+#[non_exhaustive]
+pub enum Source {
+    Import,
+    /// Typed in by hand
+    Manual,
+}
+
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    Active,
+    #[serde(other)]
+    Unknown,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        r#"/*
+ Generated by typeshare {}
+*/
+
+import Foundation
+
+/// Where an item came from
+public enum Source: RawRepresentable, Codable, Hashable {{
+	case Import
+	/// Typed in by hand
+	case Manual
+	/// A case added after this type was generated.
+	case unknown(String)
+
+	public init(rawValue: String) {{
+		switch rawValue {{
+		case "Import": self = .Import
+		case "Manual": self = .Manual
+		default: self = .unknown(rawValue)
+		}}
+	}}
+
+	public var rawValue: String {{
+		switch self {{
+		case .Import: return "Import"
+		case .Manual: return "Manual"
+		case .unknown(let value): return value
+		}}
+	}}
+
+	public init(from decoder: Decoder) throws {{
+		self.init(rawValue: try decoder.singleValueContainer().decode(String.self))
+	}}
+
+	public func encode(to encoder: Encoder) throws {{
+		var container = encoder.singleValueContainer()
+		try container.encode(rawValue)
+	}}
+}}
+
+public enum Status: RawRepresentable, Codable, Hashable {{
+	case active
+	/// A case added after this type was generated.
+	case unknown(String)
+
+	public init(rawValue: String) {{
+		switch rawValue {{
+		case "active": self = .active
+		default: self = .unknown(rawValue)
+		}}
+	}}
+
+	public var rawValue: String {{
+		switch self {{
+		case .active: return "active"
+		case .unknown(let value): return value
+		}}
+	}}
+
+	public init(from decoder: Decoder) throws {{
+		self.init(rawValue: try decoder.singleValueContainer().decode(String.self))
+	}}
+
+	public func encode(to encoder: Encoder) throws {{
+		var container = encoder.singleValueContainer()
+		try container.encode(rawValue)
+	}}
+}}
+
+"#,
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_name_the_unknown_case_apart_from_user_cases() {
+    let mut lang = swift::Swift::new();
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[non_exhaustive]
+pub enum Status {
+    Known,
+    Unknown,
+}
+
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum Health {
+    Healthy,
+    Unknown,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        r#"/*
+ Generated by typeshare {}
+*/
+
+import Foundation
+
+public enum Status: RawRepresentable, Codable, Hashable {{
+	case Known
+	case Unknown
+	/// A case added after this type was generated.
+	case unknown(String)
+
+	public init(rawValue: String) {{
+		switch rawValue {{
+		case "Known": self = .Known
+		case "Unknown": self = .Unknown
+		default: self = .unknown(rawValue)
+		}}
+	}}
+
+	public var rawValue: String {{
+		switch self {{
+		case .Known: return "Known"
+		case .Unknown: return "Unknown"
+		case .unknown(let value): return value
+		}}
+	}}
+
+	public init(from decoder: Decoder) throws {{
+		self.init(rawValue: try decoder.singleValueContainer().decode(String.self))
+	}}
+
+	public func encode(to encoder: Encoder) throws {{
+		var container = encoder.singleValueContainer()
+		try container.encode(rawValue)
+	}}
+}}
+
+public enum Health: RawRepresentable, Codable, Hashable {{
+	case healthy
+	case unknown
+	/// A case added after this type was generated.
+	case unknown_(String)
+
+	public init(rawValue: String) {{
+		switch rawValue {{
+		case "healthy": self = .healthy
+		case "unknown": self = .unknown
+		default: self = .unknown_(rawValue)
+		}}
+	}}
+
+	public var rawValue: String {{
+		switch self {{
+		case .healthy: return "healthy"
+		case .unknown: return "unknown"
+		case .unknown_(let value): return value
+		}}
+	}}
+
+	public init(from decoder: Decoder) throws {{
+		self.init(rawValue: try decoder.singleValueContainer().decode(String.self))
+	}}
+
+	public func encode(to encoder: Encoder) throws {{
+		var container = encoder.singleValueContainer()
+		try container.encode(rawValue)
+	}}
+}}
+
+"#,
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_generate_serde_repr_enums() {
+    let mut lang = swift::Swift::new();
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+/// How loud
+#[derive(Serialize_repr, Deserialize_repr)]
+#[repr(u8)]
+pub enum Level {
+    Low = 1,
+    /// Default
+    Medium,
+    High = 5,
+}
+
+#[derive(serde_repr::Serialize_repr)]
+#[repr(i16)]
+#[non_exhaustive]
+pub enum Offset {
+    Back = -1,
+    Stay,
+    Forward,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        r#"/*
+ Generated by typeshare {}
+*/
+
+import Foundation
+
+/// How loud
+public enum Level: UInt8, Codable {{
+	case Low = 1
+	/// Default
+	case Medium = 2
+	case High = 5
+}}
+
+public enum Offset: RawRepresentable, Codable, Hashable {{
+	case Back
+	case Stay
+	case Forward
+	/// A case added after this type was generated.
+	case unknown(Int16)
+
+	public init(rawValue: Int16) {{
+		switch rawValue {{
+		case -1: self = .Back
+		case 0: self = .Stay
+		case 1: self = .Forward
+		default: self = .unknown(rawValue)
+		}}
+	}}
+
+	public var rawValue: Int16 {{
+		switch self {{
+		case .Back: return -1
+		case .Stay: return 0
+		case .Forward: return 1
+		case .unknown(let value): return value
+		}}
+	}}
+
+	public init(from decoder: Decoder) throws {{
+		self.init(rawValue: try decoder.singleValueContainer().decode(Int16.self))
+	}}
+
+	public func encode(to encoder: Encoder) throws {{
+		var container = encoder.singleValueContainer()
+		try container.encode(rawValue)
+	}}
+}}
+
+"#,
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_follow_serde_into_and_from() {
+    let mut lang = swift::Swift::new();
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+/// Stored encrypted, sent as text
+#[serde(into = "String", try_from = "String")]
+pub struct Secret {
+    pub cipher: Vec<u8>,
+    pub key_id: u32,
+}
+
+#[serde(into = "Vec<String>", from = "Vec<String>")]
+pub enum Permissions {
+    Read,
+    Write,
+}
+
+#[serde(from = "LegacyPoint")]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+pub struct Vault {
+    pub secret: Secret,
+    pub permissions: Option<Permissions>,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        r#"/*
+ Generated by typeshare {}
+*/
+
+import Foundation
+
+/// Stored encrypted, sent as text
+public typealias Secret = String
+
+public typealias Permissions = [String]
+
+public struct Point: Codable {{
+	public let x: Double
+	public let y: Double
+
+	public init(x: Double, y: Double) {{
+		self.x = x
+		self.y = y
+	}}
+}}
+
+
+public extension Point {{
+	init(data: Data) throws {{
+		let decoded = try JSONDecoder().decode(Point.self, from: data)
+		self.init(x: decoded.x, y: decoded.y)
+	}}
+}}
+
+public struct Vault: Codable {{
+	public let secret: Secret
+	public let permissions: Permissions?
+
+	public init(secret: Secret, permissions: Permissions?) {{
+		self.secret = secret
+		self.permissions = permissions
+	}}
+}}
+
+
+public extension Vault {{
+	init(data: Data) throws {{
+		let decoded = try JSONDecoder().decode(Vault.self, from: data)
+		self.init(secret: decoded.secret, permissions: decoded.permissions)
+	}}
+}}
+
+"#,
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_apply_serde_as_adapters() {
+    let mut lang = swift::Swift::new();
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[serde_as]
+pub struct Account {
+    #[serde_as(as = "DisplayFromStr")]
+    pub balance: u64,
+    #[serde_as(as = "Base64")]
+    pub public_key: Vec<u8>,
+    #[serde_as(as = "TimestampSeconds")]
+    pub created_at: SystemTime,
+    #[serde_as(as = "TimestampSeconds<String>")]
+    pub updated_at: SystemTime,
+    #[serde_as(as = "DurationSeconds")]
+    pub session_length: Duration,
+    #[serde_as(as = "DurationMilliSeconds<f64>")]
+    pub average_latency: Duration,
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    pub limits: Vec<u64>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub overdraft: Option<u64>,
+    #[serde_as(as = "HashMap<DisplayFromStr, _>")]
+    pub notes: HashMap<u32, String>,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        r#"/*
+ Generated by typeshare {}
+*/
+
+import Foundation
+
+public struct Account: Codable {{
+	public let balance: String
+	public let public_key: Data
+	public let created_at: Int64
+	public let updated_at: String
+	public let session_length: UInt64
+	public let average_latency: Double
+	public let limits: [String]
+	public let overdraft: String?
+	public let notes: [String: String]
+
+	public init(balance: String, public_key: Data, created_at: Int64, updated_at: String, session_length: UInt64, average_latency: Double, limits: [String], overdraft: String?, notes: [String: String]) {{
+		self.balance = balance
+		self.public_key = public_key
+		self.created_at = created_at
+		self.updated_at = updated_at
+		self.session_length = session_length
+		self.average_latency = average_latency
+		self.limits = limits
+		self.overdraft = overdraft
+		self.notes = notes
+	}}
+}}
+
+
+public extension Account {{
+	init(data: Data) throws {{
+		let decoded = try JSONDecoder().decode(Account.self, from: data)
+		self.init(balance: decoded.balance, public_key: decoded.public_key, created_at: decoded.created_at, updated_at: decoded.updated_at, session_length: decoded.session_length, average_latency: decoded.average_latency, limits: decoded.limits, overdraft: decoded.overdraft, notes: decoded.notes)
+	}}
+}}
+
+"#,
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_map_byte_buffers() {
+    let mut lang = swift::Swift::new();
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[typeshare]
+#[serde_as]
+#[derive(Serialize, Deserialize)]
+pub struct Blob {
+    pub raw: Vec<u8>,
+    #[serde(with = "serde_bytes")]
+    pub packed: Vec<u8>,
+    pub body: bytes::Bytes,
+    pub digest: [u8; 32],
+    #[serde_as(as = "Base64")]
+    pub encoded: Vec<u8>,
+    pub thumbnail: Option<Vec<u8>>,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        r#"/*
+ Generated by typeshare {}
+*/
+
+import Foundation
+
+public struct Blob: Codable {{
+	public let raw: Data
+	public let packed: Data
+	public let body: Data
+	/// Holds exactly 32 elements.
+	public let digest: Data
+	public let encoded: Data
+	public let thumbnail: Data?
+
+	enum CodingKeys: String, CodingKey {{
+		case raw, packed, body, digest, encoded, thumbnail
+	}}
+
+	public init(from decoder: Decoder) throws {{
+		let container = try decoder.container(keyedBy: CodingKeys.self)
+		self.raw = try Data(container.decode([UInt8].self, forKey: .raw))
+		self.packed = try Data(container.decode([UInt8].self, forKey: .packed))
+		self.body = try Data(container.decode([UInt8].self, forKey: .body))
+		self.digest = try Data(container.decode([UInt8].self, forKey: .digest))
+		self.encoded = try container.decode(Data.self, forKey: .encoded)
+		self.thumbnail = try container.decodeIfPresent([UInt8].self, forKey: .thumbnail).map {{ Data($0) }}
+	}}
+
+	public func encode(to encoder: Encoder) throws {{
+		var container = encoder.container(keyedBy: CodingKeys.self)
+		try container.encode([UInt8](raw), forKey: .raw)
+		try container.encode([UInt8](packed), forKey: .packed)
+		try container.encode([UInt8](body), forKey: .body)
+		try container.encode([UInt8](digest), forKey: .digest)
+		try container.encode(encoded, forKey: .encoded)
+		try container.encodeIfPresent(thumbnail.map {{ [UInt8]($0) }}, forKey: .thumbnail)
+	}}
+
+	public init(raw: Data, packed: Data, body: Data, digest: Data, encoded: Data, thumbnail: Data?) {{
+		self.raw = raw
+		self.packed = packed
+		self.body = body
+		self.digest = digest
+		self.encoded = encoded
+		self.thumbnail = thumbnail
+	}}
+}}
+
+
+public extension Blob {{
+	init(data: Data) throws {{
+		let decoded = try JSONDecoder().decode(Blob.self, from: data)
+		self.init(raw: decoded.raw, packed: decoded.packed, body: decoded.body, digest: decoded.digest, encoded: decoded.encoded, thumbnail: decoded.thumbnail)
+	}}
+}}
+
+"#,
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_map_results() {
+    let mut lang = swift::Swift::new();
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[typeshare]
+#[derive(Serialize, Deserialize)]
+pub struct Unlock {
+    pub outcome: Result<Vault, ApiError>,
+    pub retried: Option<Result<bool, String>>,
+    pub batch: Vec<Result<Vault, ApiError>>,
+}
+
+#[typeshare]
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Reply {
+    Single(Result<Vault, ApiError>),
+    Counts(HashMap<String, Result<u32, String>>),
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        r#"/*
+ Generated by typeshare {}
+*/
+
+import Foundation
+
+public struct Unlock: Codable {{
+	public let outcome: RustResult<Vault, ApiError>
+	public let retried: RustResult<Bool, String>?
+	public let batch: [RustResult<Vault, ApiError>]
+
+	public init(outcome: RustResult<Vault, ApiError>, retried: RustResult<Bool, String>?, batch: [RustResult<Vault, ApiError>]) {{
+		self.outcome = outcome
+		self.retried = retried
+		self.batch = batch
+	}}
+}}
+
+
+public extension Unlock {{
+	init(data: Data) throws {{
+		let decoded = try JSONDecoder().decode(Unlock.self, from: data)
+		self.init(outcome: decoded.outcome, retried: decoded.retried, batch: decoded.batch)
+	}}
+}}
+
+public enum Reply: Codable {{
+	case Single(RustResult<Vault, ApiError>)
+	case Counts([String: RustResult<UInt32, String>])
+
+	public init(from decoder: Decoder) throws {{
+		let container = try decoder.singleValueContainer()
+		if let x = try? container.decode(RustResult<Vault, ApiError>.self) {{
+			self = .Single(x)
+			return
+		}}
+		if let x = try? container.decode([String: RustResult<UInt32, String>].self) {{
+			self = .Counts(x)
+			return
+		}}
+		throw DecodingError.typeMismatch(Reply.self, DecodingError.Context(codingPath: decoder.codingPath, debugDescription: "Wrong type for Reply"))
+	}}
+
+	public func encode(to encoder: Encoder) throws {{
+		var container = encoder.singleValueContainer()
+		switch self {{
+		case .Single(let x):
+			try container.encode(x)
+		case .Counts(let x):
+			try container.encode(x)
+		}}
+	}}
+}}
+
+/// The outcome of a fallible operation, written as `{{"Ok": value}}` or `{{"Err": error}}` like Rust's `Result<T, E>`.
+public enum RustResult<T: Codable, E: Codable>: Codable {{
+	case ok(T)
+	case err(E)
+
+	enum CodingKeys: String, CodingKey {{
+		case ok = "Ok"
+		case err = "Err"
+	}}
+
+	public init(from decoder: Decoder) throws {{
+		let container = try decoder.container(keyedBy: CodingKeys.self)
+		if container.contains(.ok) {{
+			self = .ok(try container.decode(T.self, forKey: .ok))
+		}} else {{
+			self = .err(try container.decode(E.self, forKey: .err))
+		}}
+	}}
+
+	public func encode(to encoder: Encoder) throws {{
+		var container = encoder.container(keyedBy: CodingKeys.self)
+		switch self {{
+		case .ok(let value):
+			try container.encode(value, forKey: .ok)
+		case .err(let error):
+			try container.encode(error, forKey: .err)
+		}}
+	}}
+}}
+
+"#,
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_unwrap_pointers_and_references() {
+    let mut lang = swift::Swift::new();
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[typeshare]
+#[derive(Serialize, Deserialize)]
+pub struct Session<'a> {
+    pub config: Arc<Config>,
+    pub parent: Option<Box<Profile<'a>>>,
+    pub shared: std::rc::Rc<Vec<String>>,
+    pub label: Cow<'a, str>,
+    pub name: &'a str,
+    pub tags: &'a [Cow<'a, str>],
+    pub scopes: Vec<Box<str>>,
+}
+
+#[typeshare]
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Source<'a> {
+    Inline(Cow<'a, str>),
+    Shared(Arc<Config>),
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        r#"/*
+ Generated by typeshare {}
+*/
+
+import Foundation
+
+public struct Session: Codable {{
+	public let config: Config
+	public let parent: Profile?
+	public let shared: [String]
+	public let label: String
+	public let name: String
+	public let tags: [String]
+	public let scopes: [String]
+
+	public init(config: Config, parent: Profile?, shared: [String], label: String, name: String, tags: [String], scopes: [String]) {{
+		self.config = config
+		self.parent = parent
+		self.shared = shared
+		self.label = label
+		self.name = name
+		self.tags = tags
+		self.scopes = scopes
+	}}
+}}
+
+
+public extension Session {{
+	init(data: Data) throws {{
+		let decoded = try JSONDecoder().decode(Session.self, from: data)
+		self.init(config: decoded.config, parent: decoded.parent, shared: decoded.shared, label: decoded.label, name: decoded.name, tags: decoded.tags, scopes: decoded.scopes)
+	}}
+}}
+
+public enum Source: Codable {{
+	case Inline(String)
+	case Shared(Config)
+
+	public init(from decoder: Decoder) throws {{
+		let container = try decoder.singleValueContainer()
+		if let x = try? container.decode(String.self) {{
+			self = .Inline(x)
+			return
+		}}
+		if let x = try? container.decode(Config.self) {{
+			self = .Shared(x)
+			return
+		}}
+		throw DecodingError.typeMismatch(Source.self, DecodingError.Context(codingPath: decoder.codingPath, debugDescription: "Wrong type for Source"))
+	}}
+
+	public func encode(to encoder: Encoder) throws {{
+		var container = encoder.singleValueContainer()
+		switch self {{
+		case .Inline(let x):
+			try container.encode(x)
+		case .Shared(let x):
+			try container.encode(x)
+		}}
+	}}
+}}
+
+"#,
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_map_std_collections() {
+    let mut lang = swift::Swift::new();
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[typeshare]
+#[derive(Serialize, Deserialize)]
+pub struct Inventory {
+    pub history: VecDeque<String>,
+    pub prices: BTreeMap<String, f64>,
+    pub ordered: indexmap::IndexMap<String, u32>,
+    pub tags: HashSet<String>,
+    #[serde(default)]
+    pub sorted_ids: BTreeSet<u64>,
+    pub origin: [f32; 3],
+    pub matrix: [[f64; 2]; 2],
+    pub samples: Option<[u16; KEY_LEN]>,
+    pub groups: Vec<HashSet<String>>,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        r#"/*
+ Generated by typeshare {}
+*/
+
+import Foundation
+
+public struct Inventory: Codable {{
+	public let history: [String]
+	public let prices: [String: Double]
+	public let ordered: [String: UInt32]
+	public let tags: Set<String>
+	public let sorted_ids: Set<UInt64>
+	/// Holds exactly 3 elements.
+	public let origin: [Float]
+	/// Holds exactly 2 elements.
+	public let matrix: [[Double]]
+	/// Holds exactly KEY_LEN elements.
+	public let samples: [UInt16]?
+	public let groups: [Set<String>]
+
+	enum CodingKeys: String, CodingKey {{
+		case history, prices, ordered, tags, sorted_ids, origin, matrix, samples, groups
+	}}
+
+	public init(from decoder: Decoder) throws {{
+		let container = try decoder.container(keyedBy: CodingKeys.self)
+		self.history = try container.decode([String].self, forKey: .history)
+		self.prices = try container.decode([String: Double].self, forKey: .prices)
+		self.ordered = try container.decode([String: UInt32].self, forKey: .ordered)
+		self.tags = try container.decode(Set<String>.self, forKey: .tags)
+		self.sorted_ids = try container.decodeIfPresent(Set<UInt64>.self, forKey: .sorted_ids) ?? []
+		self.origin = try container.decode([Float].self, forKey: .origin)
+		self.matrix = try container.decode([[Double]].self, forKey: .matrix)
+		self.samples = try container.decodeIfPresent([UInt16].self, forKey: .samples)
+		self.groups = try container.decode([Set<String>].self, forKey: .groups)
+	}}
+
+	public init(history: [String], prices: [String: Double], ordered: [String: UInt32], tags: Set<String>, sorted_ids: Set<UInt64>, origin: [Float], matrix: [[Double]], samples: [UInt16]?, groups: [Set<String>]) {{
+		self.history = history
+		self.prices = prices
+		self.ordered = ordered
+		self.tags = tags
+		self.sorted_ids = sorted_ids
+		self.origin = origin
+		self.matrix = matrix
+		self.samples = samples
+		self.groups = groups
+	}}
+}}
+
+
+public extension Inventory {{
+	init(data: Data) throws {{
+		let decoded = try JSONDecoder().decode(Inventory.self, from: data)
+		self.init(history: decoded.history, prices: decoded.prices, ordered: decoded.ordered, tags: decoded.tags, sorted_ids: decoded.sorted_ids, origin: decoded.origin, matrix: decoded.matrix, samples: decoded.samples, groups: decoded.groups)
+	}}
+}}
+
+"#,
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_map_tuples() {
+    let mut lang = swift::Swift::new();
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[typeshare]
+#[derive(Serialize, Deserialize)]
+pub struct Route {
+    pub endpoints: (String, u16),
+    pub waypoints: Vec<(f64, f64)>,
+    pub label: Option<(String, Option<u32>, bool)>,
+    pub ack: Result<(), String>,
+}
+
+#[typeshare]
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Segment {
+    Named(String),
+    Span(u32, u32),
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        r#"/*
+ Generated by typeshare {}
+*/
+
+import Foundation
+
+public struct Route: Codable {{
+	public let endpoints: Tuple2<String, UInt16>
+	public let waypoints: [Tuple2<Double, Double>]
+	public let label: Tuple3<String, UInt32?, Bool>?
+	public let ack: RustResult<Unit, String>
+
+	public init(endpoints: Tuple2<String, UInt16>, waypoints: [Tuple2<Double, Double>], label: Tuple3<String, UInt32?, Bool>?, ack: RustResult<Unit, String>) {{
+		self.endpoints = endpoints
+		self.waypoints = waypoints
+		self.label = label
+		self.ack = ack
+	}}
+}}
+
+
+public extension Route {{
+	init(data: Data) throws {{
+		let decoded = try JSONDecoder().decode(Route.self, from: data)
+		self.init(endpoints: decoded.endpoints, waypoints: decoded.waypoints, label: decoded.label, ack: decoded.ack)
+	}}
+}}
+
+public enum Segment: Codable {{
+	case Named(String)
+	case Span(Tuple2<UInt32, UInt32>)
+
+	public init(from decoder: Decoder) throws {{
+		let container = try decoder.singleValueContainer()
+		if let x = try? container.decode(String.self) {{
+			self = .Named(x)
+			return
+		}}
+		if let x = try? container.decode(Tuple2<UInt32, UInt32>.self) {{
+			self = .Span(x)
+			return
+		}}
+		throw DecodingError.typeMismatch(Segment.self, DecodingError.Context(codingPath: decoder.codingPath, debugDescription: "Wrong type for Segment"))
+	}}
+
+	public func encode(to encoder: Encoder) throws {{
+		var container = encoder.singleValueContainer()
+		switch self {{
+		case .Named(let x):
+			try container.encode(x)
+		case .Span(let x):
+			try container.encode(x)
+		}}
+	}}
+}}
+
+/// The outcome of a fallible operation, written as `{{"Ok": value}}` or `{{"Err": error}}` like Rust's `Result<T, E>`.
+public enum RustResult<T: Codable, E: Codable>: Codable {{
+	case ok(T)
+	case err(E)
+
+	enum CodingKeys: String, CodingKey {{
+		case ok = "Ok"
+		case err = "Err"
+	}}
+
+	public init(from decoder: Decoder) throws {{
+		let container = try decoder.container(keyedBy: CodingKeys.self)
+		if container.contains(.ok) {{
+			self = .ok(try container.decode(T.self, forKey: .ok))
+		}} else {{
+			self = .err(try container.decode(E.self, forKey: .err))
+		}}
+	}}
+
+	public func encode(to encoder: Encoder) throws {{
+		var container = encoder.container(keyedBy: CodingKeys.self)
+		switch self {{
+		case .ok(let value):
+			try container.encode(value, forKey: .ok)
+		case .err(let error):
+			try container.encode(error, forKey: .err)
+		}}
+	}}
+}}
+
+/// Rust's `()`, written as `null`.
+public struct Unit: Codable {{
+	public init() {{}}
+
+	public init(from decoder: Decoder) throws {{
+		guard try decoder.singleValueContainer().decodeNil() else {{
+			throw DecodingError.typeMismatch(Unit.self, DecodingError.Context(codingPath: decoder.codingPath, debugDescription: "Expected null"))
+		}}
+	}}
+
+	public func encode(to encoder: Encoder) throws {{
+		var container = encoder.singleValueContainer()
+		try container.encodeNil()
+	}}
+}}
+
+/// A Rust tuple of 2 elements, written as a JSON array.
+public struct Tuple2<T0: Codable, T1: Codable>: Codable {{
+	public let _0: T0
+	public let _1: T1
+
+	public init(_ _0: T0, _ _1: T1) {{
+		self._0 = _0
+		self._1 = _1
+	}}
+
+	public init(from decoder: Decoder) throws {{
+		var container = try decoder.unkeyedContainer()
+		self._0 = try container.decode(T0.self)
+		self._1 = try container.decode(T1.self)
+	}}
+
+	public func encode(to encoder: Encoder) throws {{
+		var container = encoder.unkeyedContainer()
+		try container.encode(_0)
+		try container.encode(_1)
+	}}
+}}
+
+/// A Rust tuple of 3 elements, written as a JSON array.
+public struct Tuple3<T0: Codable, T1: Codable, T2: Codable>: Codable {{
+	public let _0: T0
+	public let _1: T1
+	public let _2: T2
+
+	public init(_ _0: T0, _ _1: T1, _ _2: T2) {{
+		self._0 = _0
+		self._1 = _1
+		self._2 = _2
+	}}
+
+	public init(from decoder: Decoder) throws {{
+		var container = try decoder.unkeyedContainer()
+		self._0 = try container.decode(T0.self)
+		self._1 = try container.decode(T1.self)
+		self._2 = try container.decode(T2.self)
+	}}
+
+	public func encode(to encoder: Encoder) throws {{
+		var container = encoder.unkeyedContainer()
+		try container.encode(_0)
+		try container.encode(_1)
+		try container.encode(_2)
+	}}
+}}
+
+"#,
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_map_std_types() {
+    let mut lang = swift::Swift::new();
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[typeshare]
+#[derive(Serialize, Deserialize)]
+pub struct Probe {
+    pub timeout: std::time::Duration,
+    pub started_at: SystemTime,
+    pub intervals: Vec<Duration>,
+    pub log_path: PathBuf,
+    pub peer: std::net::SocketAddr,
+    pub gateway: Option<IpAddr>,
+    pub attempts: NonZeroU32,
+    pub window: std::num::NonZero<u16>,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        r#"/*
+ Generated by typeshare {}
+*/
+
+import Foundation
+
+public struct Probe: Codable {{
+	public let timeout: RustDuration
+	public let started_at: RustSystemTime
+	public let intervals: [RustDuration]
+	public let log_path: String
+	public let peer: String
+	public let gateway: String?
+	public let attempts: UInt32
+	public let window: UInt16
+
+	public init(timeout: RustDuration, started_at: RustSystemTime, intervals: [RustDuration], log_path: String, peer: String, gateway: String?, attempts: UInt32, window: UInt16) {{
+		self.timeout = timeout
+		self.started_at = started_at
+		self.intervals = intervals
+		self.log_path = log_path
+		self.peer = peer
+		self.gateway = gateway
+		self.attempts = attempts
+		self.window = window
+	}}
+}}
+
+
+public extension Probe {{
+	init(data: Data) throws {{
+		let decoded = try JSONDecoder().decode(Probe.self, from: data)
+		self.init(timeout: decoded.timeout, started_at: decoded.started_at, intervals: decoded.intervals, log_path: decoded.log_path, peer: decoded.peer, gateway: decoded.gateway, attempts: decoded.attempts, window: decoded.window)
+	}}
+}}
+
+/// A `std::time::Duration`: whole seconds, and the nanoseconds past them.
+public struct RustDuration: Codable {{
+	public let secs: UInt64
+	public let nanos: UInt32
+
+	public init(secs: UInt64, nanos: UInt32) {{
+		self.secs = secs
+		self.nanos = nanos
+	}}
+}}
+
+
+public extension RustDuration {{
+	init(data: Data) throws {{
+		let decoded = try JSONDecoder().decode(RustDuration.self, from: data)
+		self.init(secs: decoded.secs, nanos: decoded.nanos)
+	}}
+}}
+
+/// A `std::time::SystemTime`: whole seconds since the Unix epoch, and the nanoseconds past them.
+public struct RustSystemTime: Codable {{
+	public let secs_since_epoch: UInt64
+	public let nanos_since_epoch: UInt32
+
+	public init(secs_since_epoch: UInt64, nanos_since_epoch: UInt32) {{
+		self.secs_since_epoch = secs_since_epoch
+		self.nanos_since_epoch = nanos_since_epoch
+	}}
+}}
+
+
+public extension RustSystemTime {{
+	init(data: Data) throws {{
+		let decoded = try JSONDecoder().decode(RustSystemTime.self, from: data)
+		self.init(secs_since_epoch: decoded.secs_since_epoch, nanos_since_epoch: decoded.nanos_since_epoch)
+	}}
+}}
+
+"#,
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[cfg(all(feature = "chrono", feature = "time", feature = "uuid", feature = "url", feature = "serde_json", feature = "rust_decimal"))]
+#[test]
+fn can_map_ecosystem_types() {
+    let mut lang = swift::Swift::new();
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[typeshare]
+#[derive(Serialize, Deserialize)]
+pub struct Item {
+    pub id: Uuid,
+    pub vault_id: uuid::Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<chrono::DateTime<chrono::FixedOffset>>,
+    pub birthday: NaiveDate,
+    pub expires_at: time::OffsetDateTime,
+    pub website: url::Url,
+    pub details: serde_json::Value,
+    pub extra: serde_json::Map<String, Value>,
+    pub price: rust_decimal::Decimal,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        r#"/*
+ Generated by typeshare {}
+*/
+
+import Foundation
+
+public struct Item: Codable {{
+	public let id: UUID
+	public let vault_id: UUID
+	public let created_at: Date
+	public let updated_at: Date?
+	public let birthday: String
+	public let expires_at: String
+	public let website: URL
+	public let details: AnyCodable
+	public let extra: [String: AnyCodable]
+	public let price: String
+
+	public init(id: UUID, vault_id: UUID, created_at: Date, updated_at: Date?, birthday: String, expires_at: String, website: URL, details: AnyCodable, extra: [String: AnyCodable], price: String) {{
+		self.id = id
+		self.vault_id = vault_id
+		self.created_at = created_at
+		self.updated_at = updated_at
+		self.birthday = birthday
+		self.expires_at = expires_at
+		self.website = website
+		self.details = details
+		self.extra = extra
+		self.price = price
+	}}
+}}
+
+
+public extension Item {{
+	init(data: Data) throws {{
+		let decoder = JSONDecoder()
+		decoder.dateDecodingStrategy = .rfc3339
+		let decoded = try decoder.decode(Item.self, from: data)
+		self.init(id: decoded.id, vault_id: decoded.vault_id, created_at: decoded.created_at, updated_at: decoded.updated_at, birthday: decoded.birthday, expires_at: decoded.expires_at, website: decoded.website, details: decoded.details, extra: decoded.extra, price: decoded.price)
+	}}
+}}
+
+/// Any JSON value, like Rust's `serde_json::Value`.
+public enum AnyCodable: Codable, Equatable {{
+	case null
+	case bool(Bool)
+	case number(Double)
+	case string(String)
+	case array([AnyCodable])
+	case object([String: AnyCodable])
+
+	public init(from decoder: Decoder) throws {{
+		let container = try decoder.singleValueContainer()
+		if container.decodeNil() {{
+			self = .null
+		}} else if let value = try? container.decode(Bool.self) {{
+			self = .bool(value)
+		}} else if let value = try? container.decode(Double.self) {{
+			self = .number(value)
+		}} else if let value = try? container.decode(String.self) {{
+			self = .string(value)
+		}} else if let value = try? container.decode([AnyCodable].self) {{
+			self = .array(value)
+		}} else {{
+			self = .object(try container.decode([String: AnyCodable].self))
+		}}
+	}}
+
+	public func encode(to encoder: Encoder) throws {{
+		var container = encoder.singleValueContainer()
+		switch self {{
+		case .null:
+			try container.encodeNil()
+		case .bool(let value):
+			try container.encode(value)
+		case .number(let value):
+			try container.encode(value)
+		case .string(let value):
+			try container.encode(value)
+		case .array(let value):
+			try container.encode(value)
+		case .object(let value):
+			try container.encode(value)
+		}}
+	}}
+}}
+
+public extension JSONDecoder.DateDecodingStrategy {{
+	/// Reads RFC 3339 dates as Rust's chrono writes them, with or without fractional seconds.
+	static let rfc3339 = custom {{ decoder in
+		var string = try decoder.singleValueContainer().decode(String.self)
+		if let dot = string.firstIndex(of: ".") {{
+			let digits = string[string.index(after: dot)...].prefix(while: {{ $0.isNumber }})
+			string.replaceSubrange(digits.startIndex..<digits.endIndex, with: (digits + "000").prefix(3))
+		}}
+		let formatter = ISO8601DateFormatter()
+		formatter.formatOptions = [.withInternetDateTime, .withFractionalSeconds]
+		if let date = formatter.date(from: string) {{
+			return date
+		}}
+		formatter.formatOptions = [.withInternetDateTime]
+		if let date = formatter.date(from: string) {{
+			return date
+		}}
+		throw DecodingError.dataCorrupted(DecodingError.Context(codingPath: decoder.codingPath, debugDescription: "Invalid RFC 3339 date: \(string)"))
+	}}
+}}
+
+"#,
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[cfg(all(feature = "url", feature = "serde_json"))]
+#[test]
+fn can_shadow_ecosystem_types_with_local_ones() {
+    let mut lang = swift::Swift::new();
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[typeshare]
+pub struct Value {
+    pub amount: i32,
+}
+
+#[typeshare]
+pub struct Url {
+    pub host: String,
+    pub path: String,
+}
+
+#[typeshare]
+pub struct Link {
+    pub target: Url,
+    pub price: Value,
+    pub canonical: url::Url,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        "/*
+ Generated by typeshare {}
+*/
+
+import Foundation
+
+public struct Value: Codable {{
+	public let amount: Int32
+
+	public init(amount: Int32) {{
+		self.amount = amount
+	}}
+}}
+
+
+public extension Value {{
+	init(data: Data) throws {{
+		let decoded = try JSONDecoder().decode(Value.self, from: data)
+		self.init(amount: decoded.amount)
+	}}
+}}
+
+public struct Url: Codable {{
+	public let host: String
+	public let path: String
+
+	public init(host: String, path: String) {{
+		self.host = host
+		self.path = path
+	}}
+}}
+
+
+public extension Url {{
+	init(data: Data) throws {{
+		let decoded = try JSONDecoder().decode(Url.self, from: data)
+		self.init(host: decoded.host, path: decoded.path)
+	}}
+}}
+
+public struct Link: Codable {{
+	public let target: Url
+	public let price: Value
+	public let canonical: URL
+
+	public init(target: Url, price: Value, canonical: URL) {{
+		self.target = target
+		self.price = price
+		self.canonical = canonical
+	}}
+}}
+
+
+public extension Link {{
+	init(data: Data) throws {{
+		let decoded = try JSONDecoder().decode(Link.self, from: data)
+		self.init(target: decoded.target, price: decoded.price, canonical: decoded.canonical)
+	}}
+}}
+
+",
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn can_decode_dates_in_nested_types() {
+    let mut lang = swift::Swift::new();
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[typeshare]
+pub struct Audit {
+    pub created_at: DateTime<Utc>,
+}
+
+#[typeshare]
+pub struct Document {
+    pub title: String,
+    pub audits: Vec<Audit>,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        r#"/*
+ Generated by typeshare {}
+*/
+
+import Foundation
+
+public struct Audit: Codable {{
+	public let created_at: Date
+
+	public init(created_at: Date) {{
+		self.created_at = created_at
+	}}
+}}
+
+
+public extension Audit {{
+	init(data: Data) throws {{
+		let decoder = JSONDecoder()
+		decoder.dateDecodingStrategy = .rfc3339
+		let decoded = try decoder.decode(Audit.self, from: data)
+		self.init(created_at: decoded.created_at)
+	}}
+}}
+
+public struct Document: Codable {{
+	public let title: String
+	public let audits: [Audit]
+
+	public init(title: String, audits: [Audit]) {{
+		self.title = title
+		self.audits = audits
+	}}
+}}
+
+
+public extension Document {{
+	init(data: Data) throws {{
+		let decoder = JSONDecoder()
+		decoder.dateDecodingStrategy = .rfc3339
+		let decoded = try decoder.decode(Document.self, from: data)
+		self.init(title: decoded.title, audits: decoded.audits)
+	}}
+}}
+
+public extension JSONDecoder.DateDecodingStrategy {{
+	/// Reads RFC 3339 dates as Rust's chrono writes them, with or without fractional seconds.
+	static let rfc3339 = custom {{ decoder in
+		var string = try decoder.singleValueContainer().decode(String.self)
+		if let dot = string.firstIndex(of: ".") {{
+			let digits = string[string.index(after: dot)...].prefix(while: {{ $0.isNumber }})
+			string.replaceSubrange(digits.startIndex..<digits.endIndex, with: (digits + "000").prefix(3))
+		}}
+		let formatter = ISO8601DateFormatter()
+		formatter.formatOptions = [.withInternetDateTime, .withFractionalSeconds]
+		if let date = formatter.date(from: string) {{
+			return date
+		}}
+		formatter.formatOptions = [.withInternetDateTime]
+		if let date = formatter.date(from: string) {{
+			return date
+		}}
+		throw DecodingError.dataCorrupted(DecodingError.Context(codingPath: decoder.codingPath, debugDescription: "Invalid RFC 3339 date: \(string)"))
+	}}
+}}
+
+"#,
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_map_types_from_config() {
+    let mut lang = swift::Swift::new();
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            config: Config::parse(
+                r#"
+[types]
+"crate::Secret" = { ts = "string", swift = "SecureString", java = "char[]" }
+"money::Money" = { ts = "Money", swift = "Money" }
+
+[imports]
+ts = ['import { Money } from "./money";']
+swift = ["import SecureKit"]
+"#,
+            )
+            .unwrap(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[typeshare]
+#[derive(Serialize, Deserialize)]
+pub struct Account {
+    pub password: Secret,
+    pub pin: crate::Secret,
+    pub balance: Money,
+    pub history: Vec<money::Money>,
+    pub extra: Option<Opaque>,
+    #[serde(default)]
+    pub recovery: Secret,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        r#"/*
+ Generated by typeshare {}
+*/
+
+import Foundation
+import SecureKit
+
+public struct Account: Codable {{
+	public let password: SecureString
+	public let pin: SecureString
+	public let balance: Money
+	public let history: [Money]
+	public let extra: Opaque?
+	public let recovery: SecureString?
+
+	enum CodingKeys: String, CodingKey {{
+		case password, pin, balance, history, extra, recovery
+	}}
+
+	public init(from decoder: Decoder) throws {{
+		let container = try decoder.container(keyedBy: CodingKeys.self)
+		self.password = try container.decode(SecureString.self, forKey: .password)
+		self.pin = try container.decode(SecureString.self, forKey: .pin)
+		self.balance = try container.decode(Money.self, forKey: .balance)
+		self.history = try container.decode([Money].self, forKey: .history)
+		self.extra = try container.decodeIfPresent(Opaque.self, forKey: .extra)
+		self.recovery = try container.decodeIfPresent(SecureString.self, forKey: .recovery)
+	}}
+
+	public init(password: SecureString, pin: SecureString, balance: Money, history: [Money], extra: Opaque?, recovery: SecureString?) {{
+		self.password = password
+		self.pin = pin
+		self.balance = balance
+		self.history = history
+		self.extra = extra
+		self.recovery = recovery
+	}}
+}}
+
+
+public extension Account {{
+	init(data: Data) throws {{
+		let decoded = try JSONDecoder().decode(Account.self, from: data)
+		self.init(password: decoded.password, pin: decoded.pin, balance: decoded.balance, history: decoded.history, extra: decoded.extra, recovery: decoded.recovery)
+	}}
+}}
+
+"#,
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_wrap_maps_with_integer_and_enum_keys() {
+    let mut lang = swift::Swift::new();
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[typeshare]
+#[derive(Serialize, Deserialize)]
+pub enum Color {
+    Red,
+    Green,
+}
+
+#[typeshare]
+#[derive(Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct VaultId(u32);
+
+#[typeshare]
+#[derive(Serialize, Deserialize)]
+pub struct Inventory {
+    pub names: HashMap<String, String>,
+    pub by_color: HashMap<Color, u32>,
+    pub by_vault: HashMap<VaultId, Vec<String>>,
+    pub counts: BTreeMap<u16, String>,
+    #[serde(default)]
+    pub nested: Option<Vec<HashMap<Color, bool>>>,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        r#"/*
+ Generated by typeshare {}
+*/
+
+import Foundation
+
+public typealias VaultId = UInt32
+
+public struct Inventory: Codable {{
+	public let names: [String: String]
+	public let by_color: StringKeyed<Color, UInt32>
+	public let by_vault: StringKeyed<VaultId, [String]>
+	public let counts: StringKeyed<UInt16, String>
+	public let nested: [StringKeyed<Color, Bool>]?
+
+	enum CodingKeys: String, CodingKey {{
+		case names, by_color, by_vault, counts, nested
+	}}
+
+	public init(from decoder: Decoder) throws {{
+		let container = try decoder.container(keyedBy: CodingKeys.self)
+		self.names = try container.decode([String: String].self, forKey: .names)
+		self.by_color = try container.decode(StringKeyed<Color, UInt32>.self, forKey: .by_color)
+		self.by_vault = try container.decode(StringKeyed<VaultId, [String]>.self, forKey: .by_vault)
+		self.counts = try container.decode(StringKeyed<UInt16, String>.self, forKey: .counts)
+		self.nested = try container.decodeIfPresent([StringKeyed<Color, Bool>].self, forKey: .nested)
+	}}
+
+	public init(names: [String: String], by_color: StringKeyed<Color, UInt32>, by_vault: StringKeyed<VaultId, [String]>, counts: StringKeyed<UInt16, String>, nested: [StringKeyed<Color, Bool>]?) {{
+		self.names = names
+		self.by_color = by_color
+		self.by_vault = by_vault
+		self.counts = counts
+		self.nested = nested
+	}}
+}}
+
+
+public extension Inventory {{
+	init(data: Data) throws {{
+		let decoded = try JSONDecoder().decode(Inventory.self, from: data)
+		self.init(names: decoded.names, by_color: decoded.by_color, by_vault: decoded.by_vault, counts: decoded.counts, nested: decoded.nested)
+	}}
+}}
+
+public enum Color: String, Codable {{
+	case Red = "Red"
+	case Green = "Green"
+}}
+
+/// A map key written as a string, like Rust's integer and enum keys.
+public protocol StringKey: Hashable {{
+	init?(stringKey: String)
+	var stringKey: String {{ get }}
+}}
+
+public extension StringKey where Self: LosslessStringConvertible {{
+	init?(stringKey: String) {{
+		self.init(stringKey)
+	}}
+
+	var stringKey: String {{
+		description
+	}}
+}}
+
+public extension StringKey where Self: RawRepresentable, RawValue: LosslessStringConvertible {{
+	init?(stringKey: String) {{
+		guard let rawValue = RawValue(stringKey) else {{
+			return nil
+		}}
+		self.init(rawValue: rawValue)
+	}}
+
+	var stringKey: String {{
+		rawValue.description
+	}}
+}}
+
+/// A dictionary written as a JSON object with string keys, where Swift would write an array.
+public struct StringKeyed<Key: StringKey, Value: Codable>: Codable, ExpressibleByDictionaryLiteral {{
+	public var dictionary: [Key: Value]
+
+	public init(_ dictionary: [Key: Value]) {{
+		self.dictionary = dictionary
+	}}
+
+	public init(dictionaryLiteral elements: (Key, Value)...) {{
+		self.dictionary = Dictionary(uniqueKeysWithValues: elements)
+	}}
+
+	public init(from decoder: Decoder) throws {{
+		let object = try decoder.singleValueContainer().decode([String: Value].self)
+		var dictionary = [Key: Value]()
+		for (stringKey, value) in object {{
+			guard let key = Key(stringKey: stringKey) else {{
+				throw DecodingError.dataCorrupted(DecodingError.Context(codingPath: decoder.codingPath, debugDescription: "Invalid key: \(stringKey)"))
+			}}
+			dictionary[key] = value
+		}}
+		self.dictionary = dictionary
+	}}
+
+	public func encode(to encoder: Encoder) throws {{
+		var container = encoder.singleValueContainer()
+		try container.encode(Dictionary(uniqueKeysWithValues: dictionary.map {{ ($0.key.stringKey, $0.value) }}))
+	}}
+}}
+
+extension Color: StringKey {{}}
+extension UInt32: StringKey {{}}
+extension UInt16: StringKey {{}}
+
+"#,
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_wrap_maps_with_open_enum_keys() {
+    let mut lang = swift::Swift::new();
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[typeshare]
+#[derive(Serialize, Deserialize)]
+#[non_exhaustive]
+pub enum Plan {
+    Free,
+    Team,
+}
+
+#[typeshare]
+#[derive(Serialize, Deserialize)]
+pub enum Region {
+    Eu,
+    Us,
+    #[serde(other)]
+    Other,
+}
+
+#[typeshare]
+#[derive(Serialize, Deserialize)]
+pub struct Usage {
+    pub seats: HashMap<Plan, u32>,
+    pub latency: HashMap<Region, f64>,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        r#"/*
+ Generated by typeshare {}
+*/
+
+import Foundation
+
+public struct Usage: Codable {{
+	public let seats: StringKeyed<Plan, UInt32>
+	public let latency: StringKeyed<Region, Double>
+
+	public init(seats: StringKeyed<Plan, UInt32>, latency: StringKeyed<Region, Double>) {{
+		self.seats = seats
+		self.latency = latency
+	}}
+}}
+
+
+public extension Usage {{
+	init(data: Data) throws {{
+		let decoded = try JSONDecoder().decode(Usage.self, from: data)
+		self.init(seats: decoded.seats, latency: decoded.latency)
+	}}
+}}
+
+public enum Plan: RawRepresentable, Codable, Hashable {{
+	case Free
+	case Team
+	/// A case added after this type was generated.
+	case unknown(String)
+
+	public init(rawValue: String) {{
+		switch rawValue {{
+		case "Free": self = .Free
+		case "Team": self = .Team
+		default: self = .unknown(rawValue)
+		}}
+	}}
+
+	public var rawValue: String {{
+		switch self {{
+		case .Free: return "Free"
+		case .Team: return "Team"
+		case .unknown(let value): return value
+		}}
+	}}
+
+	public init(from decoder: Decoder) throws {{
+		self.init(rawValue: try decoder.singleValueContainer().decode(String.self))
+	}}
+
+	public func encode(to encoder: Encoder) throws {{
+		var container = encoder.singleValueContainer()
+		try container.encode(rawValue)
+	}}
+}}
+
+public enum Region: RawRepresentable, Codable, Hashable {{
+	case Eu
+	case Us
+	/// A case added after this type was generated.
+	case unknown(String)
+
+	public init(rawValue: String) {{
+		switch rawValue {{
+		case "Eu": self = .Eu
+		case "Us": self = .Us
+		default: self = .unknown(rawValue)
+		}}
+	}}
+
+	public var rawValue: String {{
+		switch self {{
+		case .Eu: return "Eu"
+		case .Us: return "Us"
+		case .unknown(let value): return value
+		}}
+	}}
+
+	public init(from decoder: Decoder) throws {{
+		self.init(rawValue: try decoder.singleValueContainer().decode(String.self))
+	}}
+
+	public func encode(to encoder: Encoder) throws {{
+		var container = encoder.singleValueContainer()
+		try container.encode(rawValue)
+	}}
+}}
+
+/// A map key written as a string, like Rust's integer and enum keys.
+public protocol StringKey: Hashable {{
+	init?(stringKey: String)
+	var stringKey: String {{ get }}
+}}
+
+public extension StringKey where Self: LosslessStringConvertible {{
+	init?(stringKey: String) {{
+		self.init(stringKey)
+	}}
+
+	var stringKey: String {{
+		description
+	}}
+}}
+
+public extension StringKey where Self: RawRepresentable, RawValue: LosslessStringConvertible {{
+	init?(stringKey: String) {{
+		guard let rawValue = RawValue(stringKey) else {{
+			return nil
+		}}
+		self.init(rawValue: rawValue)
+	}}
+
+	var stringKey: String {{
+		rawValue.description
+	}}
+}}
+
+/// A dictionary written as a JSON object with string keys, where Swift would write an array.
+public struct StringKeyed<Key: StringKey, Value: Codable>: Codable, ExpressibleByDictionaryLiteral {{
+	public var dictionary: [Key: Value]
+
+	public init(_ dictionary: [Key: Value]) {{
+		self.dictionary = dictionary
+	}}
+
+	public init(dictionaryLiteral elements: (Key, Value)...) {{
+		self.dictionary = Dictionary(uniqueKeysWithValues: elements)
+	}}
+
+	public init(from decoder: Decoder) throws {{
+		let object = try decoder.singleValueContainer().decode([String: Value].self)
+		var dictionary = [Key: Value]()
+		for (stringKey, value) in object {{
+			guard let key = Key(stringKey: stringKey) else {{
+				throw DecodingError.dataCorrupted(DecodingError.Context(codingPath: decoder.codingPath, debugDescription: "Invalid key: \(stringKey)"))
+			}}
+			dictionary[key] = value
+		}}
+		self.dictionary = dictionary
+	}}
+
+	public func encode(to encoder: Encoder) throws {{
+		var container = encoder.singleValueContainer()
+		try container.encode(Dictionary(uniqueKeysWithValues: dictionary.map {{ ($0.key.stringKey, $0.value) }}))
+	}}
+}}
+
+extension Plan: StringKey {{}}
+extension Region: StringKey {{}}
+
+"#,
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_override_types_with_serialized_as() {
+    let mut lang = swift::Swift::new();
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+/// A password, written as a plain string.
+#[typeshare(serialized_as = "String")]
+pub struct Secret {
+    bytes: Vec<u8>,
+}
+
+#[typeshare(serialized_as = "u8")]
+pub enum Level {
+    Low,
+    High(u8),
+}
+
+#[typeshare(ts = "bigint", swift = "Decimal", java = "java.math.BigDecimal")]
+pub struct Amount {
+    pub units: u64,
+}
+
+#[typeshare]
+#[derive(Serialize, Deserialize)]
+pub struct Login {
+    pub password: Secret,
+    pub history: Vec<Secret>,
+    pub by_site: HashMap<String, Option<Secret>>,
+    pub levels: Vec<Level>,
+    pub balance: Amount,
+    pub amounts: Vec<Amount>,
+    #[typeshare(serialized_as = "String")]
+    pub token: Token,
+    #[typeshare(serialized_as = "Vec<String>")]
+    pub scopes: Scopes,
+    #[typeshare(ts = "`${number}px`", swift = "String", java = "String")]
+    pub width: Option<Width>,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        r#"/*
+ Generated by typeshare {}
+*/
+
+import Foundation
+
+/// A password, written as a plain string.
+public typealias Secret = String
+
+public typealias Level = UInt8
+
+public struct Login: Codable {{
+	public let password: Secret
+	public let history: [Secret]
+	public let by_site: [String: Secret?]
+	public let levels: [Level]
+	public let balance: Decimal
+	public let amounts: [Decimal]
+	public let token: String
+	public let scopes: [String]
+	public let width: String?
+
+	public init(password: Secret, history: [Secret], by_site: [String: Secret?], levels: [Level], balance: Decimal, amounts: [Decimal], token: String, scopes: [String], width: String?) {{
+		self.password = password
+		self.history = history
+		self.by_site = by_site
+		self.levels = levels
+		self.balance = balance
+		self.amounts = amounts
+		self.token = token
+		self.scopes = scopes
+		self.width = width
+	}}
+}}
+
+
+public extension Login {{
+	init(data: Data) throws {{
+		let decoded = try JSONDecoder().decode(Login.self, from: data)
+		self.init(password: decoded.password, history: decoded.history, by_site: decoded.by_site, levels: decoded.levels, balance: decoded.balance, amounts: decoded.amounts, token: decoded.token, scopes: decoded.scopes, width: decoded.width)
+	}}
+}}
+
+"#,
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn rejects_128_bit_serde_repr_enums() {
+    let mut lang = swift::Swift::new();
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[derive(Serialize_repr, Deserialize_repr)]
+#[repr(u128)]
+pub enum Huge {
+    A = 1,
+    B = 18446744073709551616,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    let err = g.process_source(source.to_string(), &mut out).unwrap_err();
+    assert_eq!(err.to_string(), "Huge is `#[repr(u128)]`, and Swift enums can't have 128-bit raw values");
+}
+
+#[test]
+fn can_leave_out_types_overridden_in_the_language() {
+    let mut lang = swift::Swift::new();
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[typeshare(ts = "string", swift = "String")]
+pub struct Width(u64, u64);
+
+#[typeshare(ts = "`${number}px` | `${number}%`")]
+pub enum Size {
+    Pixels(u32),
+    Percent(f32),
+}
+
+pub struct Layout {
+    pub width: Width,
+    pub size: Size,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        r#"/*
+ Generated by typeshare {}
+*/
+
+import Foundation
+
+public struct Layout: Codable {{
+	public let width: String
+	public let size: Size
+
+	public init(width: String, size: Size) {{
+		self.width = width
+		self.size = size
+	}}
+}}
+
+
+public extension Layout {{
+	init(data: Data) throws {{
+		let decoded = try JSONDecoder().decode(Layout.self, from: data)
+		self.init(width: decoded.width, size: decoded.size)
+	}}
+}}
+
+public enum Size: Codable {{
+	case Pixels(UInt32)
+	case Percent(Float)
+
+	public init(from decoder: Decoder) throws {{
+		let container = try decoder.container(keyedBy: AnyCodingKey.self)
+		if container.contains(AnyCodingKey(stringValue: "Pixels")!) {{
+			self = .Pixels(try container.decode(UInt32.self, forKey: AnyCodingKey(stringValue: "Pixels")!))
+			return
+		}}
+		if container.contains(AnyCodingKey(stringValue: "Percent")!) {{
+			self = .Percent(try container.decode(Float.self, forKey: AnyCodingKey(stringValue: "Percent")!))
+			return
+		}}
+		throw DecodingError.typeMismatch(Size.self, DecodingError.Context(codingPath: decoder.codingPath, debugDescription: "Wrong type for Size"))
+	}}
+
+	public func encode(to encoder: Encoder) throws {{
+		var container = encoder.container(keyedBy: AnyCodingKey.self)
+		switch self {{
+		case .Pixels(let x):
+			try container.encode(x, forKey: AnyCodingKey(stringValue: "Pixels")!)
+		case .Percent(let x):
+			try container.encode(x, forKey: AnyCodingKey(stringValue: "Percent")!)
+		}}
+	}}
+}}
+
+/// Coding key for object keys that are only known at runtime.
+public struct AnyCodingKey: CodingKey {{
+	public var stringValue: String
+	public var intValue: Int? {{ return nil }}
+
+	public init?(stringValue: String) {{
+		self.stringValue = stringValue
+	}}
+
+	public init?(intValue: Int) {{
+		return nil
+	}}
+}}
+
 "#,
         env!("CARGO_PKG_VERSION")
     );
@@ -1,5 +1,5 @@
-use text_diff;
-use typeshare::language::{Generator, Params};
+use typeshare::config::Config;
+use typeshare::language::{Generator, Params, TypeScriptLargeIntegers};
 use typeshare::typescript;
 
 #[test]
@@ -11,6 +11,7 @@ fn can_generate_simple_struct_with_a_comment() {
             use_marker: false,
             swift_prefix: "".to_string(),
             java_package: "".to_string(),
+            ..Default::default()
         },
     );
 
@@ -38,7 +39,7 @@ pub struct Person {
 export interface Person {{
 	name: string;
 	age: number;
-	info?: string;
+	info?: string | null;
 	emails: string[];
 }}
 
@@ -61,6 +62,7 @@ fn can_handle_serde_rename() {
             use_marker: false,
             swift_prefix: "".to_string(),
             java_package: "".to_string(),
+            ..Default::default()
         },
     );
 
@@ -91,7 +93,7 @@ export interface Person {{
 	name: string;
 	age: number;
 	extraSpecialFieldOne: number;
-	extraSpecialFieldTwo?: string[];
+	extraSpecialFieldTwo?: string[] | null;
 }}
 
 ",
@@ -113,6 +115,7 @@ fn can_handle_serde_rename_all() {
             use_marker: false,
             swift_prefix: "".to_string(),
             java_package: "".to_string(),
+            ..Default::default()
         },
     );
 
@@ -152,7 +155,7 @@ export interface Person {{
 	lastName: string;
 	age: number;
 	extraSpecialField1: number;
-	extraSpecialField2?: string[];
+	extraSpecialField2?: string[] | null;
 }}
 
 // This is a Person2 struct with UPPERCASE rename
@@ -181,6 +184,7 @@ fn can_generate_simple_enum() {
             use_marker: false,
             swift_prefix: "".to_string(),
             java_package: "".to_string(),
+            ..Default::default()
         },
     );
 
@@ -220,6 +224,78 @@ export enum Colors {{
     assert_eq!(expected, result);
 }
 
+#[test]
+fn can_apply_enum_tagging_to_struct_variants() {
+    let mut lang = typescript::TypeScript {};
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[typeshare]
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Shape {
+    Circle { radius: f64 },
+    Rect { width: f64, height: f64 },
+}
+
+#[typeshare]
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "t", content = "c")]
+pub enum Event {
+    Moved { x: i32, y: i32 },
+    Renamed(String),
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        r#"/*
+ Generated by typeshare {}
+*/
+
+export interface ShapeCircle {{
+	radius: number;
+}}
+
+export interface ShapeRect {{
+	width: number;
+	height: number;
+}}
+
+export interface EventMoved {{
+	x: number;
+	y: number;
+}}
+
+export type Shape = 
+	| ({{ type: "circle" }} & ShapeCircle)
+	| ({{ type: "rect" }} & ShapeRect);
+
+export type Event = 
+	| {{ t: "Moved"; c: EventMoved }}
+	| {{ t: "Renamed"; c: string }};
+
+"#,
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
 #[test]
 fn can_generate_algebraic_enum() {
     let mut lang = typescript::TypeScript {};
@@ -229,6 +305,7 @@ fn can_generate_algebraic_enum() {
             use_marker: false,
             swift_prefix: "".to_string(),
             java_package: "".to_string(),
+            ..Default::default()
         },
     );
 
@@ -254,10 +331,2385 @@ pub enum AdvancedColors {
 
 // Enum comment
 export type AdvancedColors = 
-	| string	// This is a case comment
-	| number
-	| number[]
-	| ItemDetailsFieldValue;	// Comment on the last element
+	| {{ string: string }}	// This is a case comment
+	| {{ number: number }}
+	| {{ numberArray: number[] }}
+	| {{ reallyCoolType: ItemDetailsFieldValue }};	// Comment on the last element
+
+",
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_handle_serde_skip() {
+    let mut lang = typescript::TypeScript {};
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+pub struct Vault {
+    pub name: String,
+    #[serde(skip)]
+    pub cache: Vec<u8>,
+    #[serde(skip_serializing)]
+    pub password: String,
+    #[serde(skip_deserializing)]
+    pub created_at: u64,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        "/*
+ Generated by typeshare {}
+*/
+
+export interface Vault {{
+	name: string;
+	password?: string;
+	created_at?: number;
+}}
+
+",
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_split_one_way_fields_into_request_and_response() {
+    let mut lang = typescript::TypeScript {};
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            split_directions: true,
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+/// A vault.
+pub struct Vault {
+    pub name: String,
+    #[serde(skip_serializing)]
+    pub password: String,
+    #[serde(skip_deserializing)]
+    pub created_at: u64,
+}
+
+pub struct Item {
+    pub title: String,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        "/*
+ Generated by typeshare {}
+*/
+
+// A vault.
+export interface Vault {{
+	name: string;
+	password?: string;
+	created_at?: number;
+}}
+
+// A vault.
+export interface VaultRequest {{
+	name: string;
+	password: string;
+}}
+
+// A vault.
+export interface VaultResponse {{
+	name: string;
+	created_at: number;
+}}
+
+export interface Item {{
+	title: string;
+}}
+
+",
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_keep_split_types_referable_from_other_types() {
+    let mut lang = typescript::TypeScript {};
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            split_directions: true,
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[typeshare]
+#[derive(Serialize, Deserialize)]
+pub struct Login {
+    pub username: String,
+    #[serde(skip_serializing)]
+    pub password: String,
+}
+
+#[typeshare]
+#[derive(Serialize, Deserialize)]
+#[serde(into = "Vec<u32>", from = "String")]
+pub struct Ids {
+    pub values: Vec<u32>,
+}
+
+#[typeshare]
+#[derive(Serialize, Deserialize)]
+pub struct Envelope {
+    pub login: Login,
+    pub ids: Ids,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        "/*
+ Generated by typeshare {}
+*/
+
+export interface Login {{
+	username: string;
+	password?: string;
+}}
+
+export interface LoginRequest {{
+	username: string;
+	password: string;
+}}
+
+export interface LoginResponse {{
+	username: string;
+}}
+
+export type Ids = number[];
+
+export type IdsRequest = string;
+
+export type IdsResponse = number[];
+
+export interface Envelope {{
+	login: Login;
+	ids: Ids;
+}}
+
+",
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_handle_serde_default() {
+    let mut lang = typescript::TypeScript {};
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            split_directions: true,
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+pub struct Settings {
+    pub name: String,
+    #[serde(default)]
+    pub retries: u32,
+    #[serde(default = "default_tags")]
+    pub tags: Vec<String>,
+}
+
+#[serde(default)]
+pub struct Theme {
+    pub dark: bool,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        "/*
+ Generated by typeshare {}
+*/
+
+export interface Settings {{
+	name: string;
+	retries: number;
+	tags: string[];
+}}
+
+export interface SettingsRequest {{
+	name: string;
+	retries?: number;
+	tags?: string[];
+}}
+
+export interface SettingsResponse {{
+	name: string;
+	retries: number;
+	tags: string[];
+}}
+
+export interface Theme {{
+	dark: boolean;
+}}
+
+export interface ThemeRequest {{
+	dark?: boolean;
+}}
+
+export interface ThemeResponse {{
+	dark: boolean;
+}}
+
+",
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_handle_serde_flatten() {
+    let mut lang = typescript::TypeScript {};
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+pub struct Vault {
+    pub name: String,
+    #[serde(flatten)]
+    pub common: CommonFields,
+    #[serde(flatten)]
+    pub audit: Option<AuditFields>,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        "/*
+ Generated by typeshare {}
+*/
+
+export type Vault = {{
+	name: string;
+}} & CommonFields & Partial<AuditFields>;
+
+",
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_extend_interfaces_and_intersect_other_flattened_types() {
+    let mut lang = typescript::TypeScript {};
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+pub struct Meta {
+    pub created_at: String,
+}
+
+#[serde(untagged)]
+pub enum Shape {
+    Circle(Circle),
+    Square(Square),
+}
+
+pub struct Item {
+    pub name: String,
+    #[serde(flatten)]
+    pub meta: Meta,
+    #[serde(flatten)]
+    pub shape: Option<Shape>,
+}
+
+pub struct Plain {
+    pub id: u32,
+    #[serde(flatten)]
+    pub meta: Option<Meta>,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        "/*
+ Generated by typeshare {}
+*/
+
+export interface Meta {{
+	created_at: string;
+}}
+
+export type Item = {{
+	name: string;
+}} & Meta & Partial<Shape>;
+
+export interface Plain extends Partial<Meta> {{
+	id: number;
+}}
+
+export type Shape = 
+	| Circle
+	| Square;
+
+",
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_handle_flattened_catch_all_map() {
+    let mut lang = typescript::TypeScript {};
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+pub struct Item {
+    pub title: String,
+    /// Fields added by newer servers.
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        "/*
+ Generated by typeshare {}
+*/
+
+export interface Item {{
+	title: string;
+	// Fields added by newer servers.
+	[key: string]: unknown;
+}}
+
+",
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_distinguish_missing_and_null_values() {
+    let mut lang = typescript::TypeScript {};
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            split_directions: true,
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+pub struct ItemPatch {
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "::serde_with::rust::double_option")]
+    pub url: Option<Option<String>>,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        "/*
+ Generated by typeshare {}
+*/
+
+export interface ItemPatch {{
+	title?: string | null;
+	notes?: string | null;
+	url?: string | null;
+}}
+
+export interface ItemPatchRequest {{
+	title?: string | null;
+	notes?: string | null;
+	url?: string | null;
+}}
+
+export interface ItemPatchResponse {{
+	title: string | null;
+	notes?: string;
+	url?: string | null;
+}}
+
+",
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_handle_per_direction_renames() {
+    let mut lang = typescript::TypeScript {};
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            split_directions: true,
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+pub struct Item {
+    #[serde(rename(serialize = "itemTitle", deserialize = "title"), alias = "name")]
+    pub item_title: String,
+    #[serde(rename = "body")]
+    pub notes: String,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        "/*
+ Generated by typeshare {}
+*/
+
+export interface Item {{
+	itemTitle: string;
+	body: string;
+}}
+
+export interface ItemRequest {{
+	title: string;
+	body: string;
+}}
+
+export interface ItemResponse {{
+	itemTitle: string;
+	body: string;
+}}
+
+",
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_handle_serde_transparent() {
+    let mut lang = typescript::TypeScript {};
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+/// Identifies a vault
+#[serde(transparent)]
+pub struct VaultId(String);
+
+#[serde(transparent)]
+pub struct Nickname {
+    pub value: Option<String>,
+}
+
+pub struct Vault {
+    pub id: VaultId,
+    pub tags: Vec<String>,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        "/*
+ Generated by typeshare {}
+*/
+
+// Identifies a vault
+export type VaultId = string;
+
+export type Nickname = string | null;
+
+export interface Vault {{
+	id: VaultId;
+	tags: string[];
+}}
+
+",
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_apply_rename_all_in_every_position() {
+    let mut lang = typescript::TypeScript {};
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            split_directions: true,
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[serde(rename_all(serialize = "SCREAMING-KEBAB-CASE"))]
+pub struct Settings {
+    pub dark_mode: bool,
+    pub font_size2: u32,
+}
+
+#[serde(rename_all = "camelCase", rename_all_fields = "PascalCase")]
+pub enum Shape {
+    UnitSquare(u32),
+    Circle { center_x: f64, center_y: f64 },
+    #[serde(rename_all = "kebab-case")]
+    RoundedRect { corner_radius: f64 },
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        "/*
+ Generated by typeshare {}
+*/
+
+export interface Settings {{
+	\"DARK-MODE\": boolean;
+	\"FONT-SIZE2\": number;
+}}
+
+export interface SettingsRequest {{
+	dark_mode: boolean;
+	font_size2: number;
+}}
+
+export interface SettingsResponse {{
+	\"DARK-MODE\": boolean;
+	\"FONT-SIZE2\": number;
+}}
+
+export interface ShapeCircle {{
+	CenterX: number;
+	CenterY: number;
+}}
+
+export interface ShapeRoundedRect {{
+	\"corner-radius\": number;
+}}
+
+export type Shape = 
+	| {{ unitSquare: number }}
+	| {{ circle: ShapeCircle }}
+	| {{ roundedRect: ShapeRoundedRect }};
+
+",
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_handle_deny_unknown_fields() {
+    let mut lang = typescript::TypeScript {};
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            typescript_check_unknown_fields: true,
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[serde(deny_unknown_fields)]
+pub struct Login {
+    #[serde(alias = "user")]
+    pub username: String,
+    pub password: String,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        "/*
+ Generated by typeshare {}
+*/
+
+export interface Login {{
+	username: string;
+	password: string;
+}}
+
+export type ExactLogin<T extends Login> = T & Record<Exclude<keyof T, keyof Login>, never>;
+
+export function checkLoginFields(value: object): void {{
+	const known = [\"username\", \"user\", \"password\"];
+	const unknown = Object.keys(value).find((key) => !known.includes(key));
+	if (unknown !== undefined) {{
+		throw new Error(`unknown field \\`${{unknown}}\\` in Login`);
+	}}
+}}
+
+",
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_check_aliases_only_in_request_types() {
+    let mut lang = typescript::TypeScript {};
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            split_directions: true,
+            typescript_check_unknown_fields: true,
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[serde(deny_unknown_fields)]
+pub struct Login {
+    #[serde(alias = "user")]
+    pub username: String,
+    #[serde(skip_serializing)]
+    pub password: String,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        r#"/*
+ Generated by typeshare {}
+*/
+
+export interface Login {{
+	username: string;
+	password?: string;
+}}
+
+export type ExactLogin<T extends Login> = T & Record<Exclude<keyof T, keyof Login>, never>;
+
+export function checkLoginFields(value: object): void {{
+	const known = ["username", "user", "password"];
+	const unknown = Object.keys(value).find((key) => !known.includes(key));
+	if (unknown !== undefined) {{
+		throw new Error(`unknown field \`${{unknown}}\` in Login`);
+	}}
+}}
+
+export interface LoginRequest {{
+	username: string;
+	password: string;
+}}
+
+export type ExactLoginRequest<T extends LoginRequest> = T & Record<Exclude<keyof T, keyof LoginRequest>, never>;
+
+export function checkLoginRequestFields(value: object): void {{
+	const known = ["username", "user", "password"];
+	const unknown = Object.keys(value).find((key) => !known.includes(key));
+	if (unknown !== undefined) {{
+		throw new Error(`unknown field \`${{unknown}}\` in LoginRequest`);
+	}}
+}}
+
+export interface LoginResponse {{
+	username: string;
+}}
+
+export type ExactLoginResponse<T extends LoginResponse> = T & Record<Exclude<keyof T, keyof LoginResponse>, never>;
+
+export function checkLoginResponseFields(value: object): void {{
+	const known = ["username"];
+	const unknown = Object.keys(value).find((key) => !known.includes(key));
+	if (unknown !== undefined) {{
+		throw new Error(`unknown field \`${{unknown}}\` in LoginResponse`);
+	}}
+}}
+
+"#,
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_widen_non_exhaustive_enums() {
+    let mut lang = typescript::TypeScript {};
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+/// Where an item came from
+#[non_exhaustive]
+pub enum Source {
+    Import,
+    /// Typed in by hand
+    Manual,
+}
+
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    Active,
+    #[serde(other)]
+    Unknown,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        "/*
+ Generated by typeshare {}
+*/
+
+// Where an item came from
+export const Source = {{
+	Import: \"Import\",
+	// Typed in by hand
+	Manual: \"Manual\",
+}} as const;
+
+export type Source = (typeof Source)[keyof typeof Source] | (string & {{}});
+
+export const Status = {{
+	Active: \"active\",
+	Unknown: \"unknown\",
+}} as const;
+
+export type Status = (typeof Status)[keyof typeof Status] | (string & {{}});
+
+",
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_generate_serde_repr_enums() {
+    let mut lang = typescript::TypeScript {};
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+/// How loud
+#[derive(Serialize_repr, Deserialize_repr)]
+#[repr(u8)]
+pub enum Level {
+    Low = 1,
+    /// Default
+    Medium,
+    High = 5,
+}
+
+#[derive(serde_repr::Serialize_repr)]
+#[repr(i16)]
+#[non_exhaustive]
+pub enum Offset {
+    Back = -1,
+    Stay,
+    Forward,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        "/*
+ Generated by typeshare {}
+*/
+
+// How loud
+export enum Level {{
+	Low = 1,
+	// Default
+	Medium = 2,
+	High = 5,
+}}
+
+export const Offset = {{
+	Back: -1,
+	Stay: 0,
+	Forward: 1,
+}} as const;
+
+export type Offset = (typeof Offset)[keyof typeof Offset] | (number & {{}});
+
+",
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_follow_serde_into_and_from() {
+    let mut lang = typescript::TypeScript {};
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            split_directions: true,
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+/// Stored encrypted, sent as text
+#[serde(into = "String", try_from = "String")]
+pub struct Secret {
+    pub cipher: Vec<u8>,
+    pub key_id: u32,
+}
+
+#[serde(into = "Vec<String>", from = "Vec<String>")]
+pub enum Permissions {
+    Read,
+    Write,
+}
+
+#[serde(from = "LegacyPoint")]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+pub struct Vault {
+    pub secret: Secret,
+    pub permissions: Option<Permissions>,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        "/*
+ Generated by typeshare {}
+*/
+
+// Stored encrypted, sent as text
+export type Secret = string;
+
+export type Permissions = string[];
+
+export interface Point {{
+	x: number;
+	y: number;
+}}
+
+export type PointRequest = LegacyPoint;
+
+export interface PointResponse {{
+	x: number;
+	y: number;
+}}
+
+export interface Vault {{
+	secret: Secret;
+	permissions?: Permissions | null;
+}}
+
+",
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_apply_serde_as_adapters() {
+    let mut lang = typescript::TypeScript {};
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[serde_as]
+pub struct Account {
+    #[serde_as(as = "DisplayFromStr")]
+    pub balance: u64,
+    #[serde_as(as = "Base64")]
+    pub public_key: Vec<u8>,
+    #[serde_as(as = "TimestampSeconds")]
+    pub created_at: SystemTime,
+    #[serde_as(as = "TimestampSeconds<String>")]
+    pub updated_at: SystemTime,
+    #[serde_as(as = "DurationSeconds")]
+    pub session_length: Duration,
+    #[serde_as(as = "DurationMilliSeconds<f64>")]
+    pub average_latency: Duration,
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    pub limits: Vec<u64>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub overdraft: Option<u64>,
+    #[serde_as(as = "HashMap<DisplayFromStr, _>")]
+    pub notes: HashMap<u32, String>,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        "/*
+ Generated by typeshare {}
+*/
+
+export interface Account {{
+	balance: string;
+	public_key: string;
+	created_at: number;
+	updated_at: string;
+	session_length: number;
+	average_latency: number;
+	limits: string[];
+	overdraft?: string | null;
+	notes: Record<string, string>;
+}}
+
+",
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_map_byte_buffers() {
+    let mut lang = typescript::TypeScript {};
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[typeshare]
+#[serde_as]
+#[derive(Serialize, Deserialize)]
+pub struct Blob {
+    pub raw: Vec<u8>,
+    #[serde(with = "serde_bytes")]
+    pub packed: Vec<u8>,
+    pub body: bytes::Bytes,
+    pub digest: [u8; 32],
+    #[serde_as(as = "Base64")]
+    pub encoded: Vec<u8>,
+    pub thumbnail: Option<Vec<u8>>,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        "/*
+ Generated by typeshare {}
+*/
+
+export interface Blob {{
+	raw: number[];
+	packed: number[];
+	body: number[];
+	digest: number[];
+	encoded: string;
+	thumbnail?: number[] | null;
+}}
+
+",
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_map_byte_buffers_to_uint8array() {
+    let mut lang = typescript::TypeScript {};
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            typescript_uint8array: true,
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[typeshare]
+#[serde_as]
+#[derive(Serialize, Deserialize)]
+pub struct Blob {
+    pub raw: Vec<u8>,
+    #[serde(with = "serde_bytes")]
+    pub packed: Vec<u8>,
+    pub body: bytes::Bytes,
+    pub digest: [u8; 32],
+    #[serde_as(as = "Base64")]
+    pub encoded: Vec<u8>,
+    pub thumbnail: Option<Vec<u8>>,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        "/*
+ Generated by typeshare {}
+*/
+
+export function bytesFromJson(value: string | number[]): Uint8Array {{
+	if (typeof value !== \"string\") {{
+		return Uint8Array.from(value);
+	}}
+	return Uint8Array.from(atob(value), (c) => c.charCodeAt(0));
+}}
+
+export function bytesToBase64(bytes: Uint8Array): string {{
+	return btoa(String.fromCharCode(...bytes));
+}}
+
+export function bytesToArray(bytes: Uint8Array): number[] {{
+	return Array.from(bytes);
+}}
+
+export interface Blob {{
+	raw: Uint8Array;
+	packed: Uint8Array;
+	body: Uint8Array;
+	digest: Uint8Array;
+	encoded: Uint8Array;
+	thumbnail?: Uint8Array | null;
+}}
+
+",
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_map_results() {
+    let mut lang = typescript::TypeScript {};
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[typeshare]
+#[derive(Serialize, Deserialize)]
+pub struct Unlock {
+    pub outcome: Result<Vault, ApiError>,
+    pub retried: Option<Result<bool, String>>,
+    pub batch: Vec<Result<Vault, ApiError>>,
+}
+
+#[typeshare]
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Reply {
+    Single(Result<Vault, ApiError>),
+    Counts(HashMap<String, Result<u32, String>>),
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        "/*
+ Generated by typeshare {}
+*/
+
+export interface Unlock {{
+	outcome: {{ Ok: Vault }} | {{ Err: ApiError }};
+	retried?: {{ Ok: boolean }} | {{ Err: string }} | null;
+	batch: ({{ Ok: Vault }} | {{ Err: ApiError }})[];
+}}
+
+export type Reply = 
+	| {{ Ok: Vault }} | {{ Err: ApiError }}
+	| Record<string, {{ Ok: number }} | {{ Err: string }}>;
+
+",
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_unwrap_pointers_and_references() {
+    let mut lang = typescript::TypeScript {};
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[typeshare]
+#[derive(Serialize, Deserialize)]
+pub struct Session<'a> {
+    pub config: Arc<Config>,
+    pub parent: Option<Box<Profile<'a>>>,
+    pub shared: std::rc::Rc<Vec<String>>,
+    pub label: Cow<'a, str>,
+    pub name: &'a str,
+    pub tags: &'a [Cow<'a, str>],
+    pub scopes: Vec<Box<str>>,
+}
+
+#[typeshare]
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Source<'a> {
+    Inline(Cow<'a, str>),
+    Shared(Arc<Config>),
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        "/*
+ Generated by typeshare {}
+*/
+
+export interface Session {{
+	config: Config;
+	parent?: Profile | null;
+	shared: string[];
+	label: string;
+	name: string;
+	tags: string[];
+	scopes: string[];
+}}
+
+export type Source = 
+	| string
+	| Config;
+
+",
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_map_std_collections() {
+    let mut lang = typescript::TypeScript {};
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[typeshare]
+#[derive(Serialize, Deserialize)]
+pub struct Inventory {
+    pub history: VecDeque<String>,
+    pub prices: BTreeMap<String, f64>,
+    pub ordered: indexmap::IndexMap<String, u32>,
+    pub tags: HashSet<String>,
+    #[serde(default)]
+    pub sorted_ids: BTreeSet<u64>,
+    pub origin: [f32; 3],
+    pub matrix: [[f64; 2]; 2],
+    pub samples: Option<[u16; KEY_LEN]>,
+    pub groups: Vec<HashSet<String>>,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        "/*
+ Generated by typeshare {}
+*/
+
+export interface Inventory {{
+	history: string[];
+	prices: Record<string, number>;
+	ordered: Record<string, number>;
+	tags: string[];
+	sorted_ids: number[];
+	origin: [number, number, number];
+	matrix: [[number, number], [number, number]];
+	samples?: number[] | null;
+	groups: string[][];
+}}
+
+",
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_map_sets_to_set() {
+    let mut lang = typescript::TypeScript {};
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            typescript_set: true,
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[typeshare]
+#[derive(Serialize, Deserialize)]
+pub struct Inventory {
+    pub history: VecDeque<String>,
+    pub prices: BTreeMap<String, f64>,
+    pub ordered: indexmap::IndexMap<String, u32>,
+    pub tags: HashSet<String>,
+    #[serde(default)]
+    pub sorted_ids: BTreeSet<u64>,
+    pub origin: [f32; 3],
+    pub matrix: [[f64; 2]; 2],
+    pub samples: Option<[u16; KEY_LEN]>,
+    pub groups: Vec<HashSet<String>>,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        "/*
+ Generated by typeshare {}
+*/
+
+export interface Inventory {{
+	history: string[];
+	prices: Record<string, number>;
+	ordered: Record<string, number>;
+	tags: Set<string>;
+	sorted_ids: Set<number>;
+	origin: [number, number, number];
+	matrix: [[number, number], [number, number]];
+	samples?: number[] | null;
+	groups: Set<string>[];
+}}
+
+",
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_map_tuples() {
+    let mut lang = typescript::TypeScript {};
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[typeshare]
+#[derive(Serialize, Deserialize)]
+pub struct Route {
+    pub endpoints: (String, u16),
+    pub waypoints: Vec<(f64, f64)>,
+    pub label: Option<(String, Option<u32>, bool)>,
+    pub ack: Result<(), String>,
+}
+
+#[typeshare]
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Segment {
+    Named(String),
+    Span(u32, u32),
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        "/*
+ Generated by typeshare {}
+*/
+
+export interface Route {{
+	endpoints: [string, number];
+	waypoints: [number, number][];
+	label?: [string, number | null, boolean] | null;
+	ack: {{ Ok: null }} | {{ Err: string }};
+}}
+
+export type Segment = 
+	| string
+	| [number, number];
+
+",
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_map_std_types() {
+    let mut lang = typescript::TypeScript {};
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[typeshare]
+#[derive(Serialize, Deserialize)]
+pub struct Probe {
+    pub timeout: std::time::Duration,
+    pub started_at: SystemTime,
+    pub intervals: Vec<Duration>,
+    pub log_path: PathBuf,
+    pub peer: std::net::SocketAddr,
+    pub gateway: Option<IpAddr>,
+    pub attempts: NonZeroU32,
+    pub window: std::num::NonZero<u16>,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        "/*
+ Generated by typeshare {}
+*/
+
+export interface Probe {{
+	timeout: RustDuration;
+	started_at: RustSystemTime;
+	intervals: RustDuration[];
+	log_path: string;
+	peer: string;
+	gateway?: string | null;
+	attempts: number;
+	window: number;
+}}
+
+// A `std::time::Duration`: whole seconds, and the nanoseconds past them.
+export interface RustDuration {{
+	secs: number;
+	nanos: number;
+}}
+
+// A `std::time::SystemTime`: whole seconds since the Unix epoch, and the nanoseconds past them.
+export interface RustSystemTime {{
+	secs_since_epoch: number;
+	nanos_since_epoch: number;
+}}
+
+",
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_shadow_std_types_with_local_ones() {
+    let mut lang = typescript::TypeScript {};
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[typeshare]
+pub struct Duration {
+    pub millis: u32,
+}
+
+#[typeshare]
+pub struct Path {
+    pub segments: Vec<String>,
+}
+
+#[typeshare]
+pub struct Route {
+    pub path: Path,
+    pub timeout: Duration,
+    pub retry_after: std::time::Duration,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        "/*
+ Generated by typeshare {}
+*/
+
+export interface Duration {{
+	millis: number;
+}}
+
+export interface Path {{
+	segments: string[];
+}}
+
+export interface Route {{
+	path: Path;
+	timeout: Duration;
+	retry_after: RustDuration;
+}}
+
+// A `std::time::Duration`: whole seconds, and the nanoseconds past them.
+export interface RustDuration {{
+	secs: number;
+	nanos: number;
+}}
+
+",
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+    assert_eq!(
+        g.warnings(),
+        ["RustDuration.secs: `u64` is typed as `number` in TypeScript, which rounds values beyond 2^53"]
+    );
+}
+
+#[cfg(all(feature = "chrono", feature = "time", feature = "uuid", feature = "url", feature = "serde_json", feature = "rust_decimal"))]
+#[test]
+fn can_map_ecosystem_types() {
+    let mut lang = typescript::TypeScript {};
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[typeshare]
+#[derive(Serialize, Deserialize)]
+pub struct Item {
+    pub id: Uuid,
+    pub vault_id: uuid::Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<chrono::DateTime<chrono::FixedOffset>>,
+    pub birthday: NaiveDate,
+    pub expires_at: time::OffsetDateTime,
+    pub website: url::Url,
+    pub details: serde_json::Value,
+    pub extra: serde_json::Map<String, Value>,
+    pub price: rust_decimal::Decimal,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        "/*
+ Generated by typeshare {}
+*/
+
+export interface Item {{
+	id: string;
+	vault_id: string;
+	created_at: string;
+	updated_at?: string | null;
+	birthday: string;
+	expires_at: string;
+	website: string;
+	details: unknown;
+	extra: Record<string, unknown>;
+	price: string;
+}}
+
+",
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[cfg(all(feature = "url", feature = "serde_json"))]
+#[test]
+fn can_shadow_ecosystem_types_with_local_ones() {
+    let mut lang = typescript::TypeScript {};
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[typeshare]
+pub struct Value {
+    pub amount: i32,
+}
+
+#[typeshare]
+pub struct Url {
+    pub host: String,
+    pub path: String,
+}
+
+#[typeshare]
+pub struct Link {
+    pub target: Url,
+    pub price: Value,
+    pub canonical: url::Url,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        "/*
+ Generated by typeshare {}
+*/
+
+export interface Value {{
+	amount: number;
+}}
+
+export interface Url {{
+	host: string;
+	path: string;
+}}
+
+export interface Link {{
+	target: Url;
+	price: Value;
+	canonical: string;
+}}
+
+",
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_map_types_from_config() {
+    let mut lang = typescript::TypeScript {};
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            config: Config::parse(
+                r#"
+[types]
+"crate::Secret" = { ts = "string", swift = "SecureString", java = "char[]" }
+"money::Money" = { ts = "Money", swift = "Money" }
+
+[imports]
+ts = ['import { Money } from "./money";']
+swift = ["import SecureKit"]
+"#,
+            )
+            .unwrap(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[typeshare]
+#[derive(Serialize, Deserialize)]
+pub struct Account {
+    pub password: Secret,
+    pub pin: crate::Secret,
+    pub balance: Money,
+    pub history: Vec<money::Money>,
+    pub extra: Option<Opaque>,
+    #[serde(default)]
+    pub recovery: Secret,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        "/*
+ Generated by typeshare {}
+*/
+
+import {{ Money }} from \"./money\";
+
+export interface Account {{
+	password: string;
+	pin: string;
+	balance: Money;
+	history: Money[];
+	extra?: Opaque | null;
+	recovery: string;
+}}
+
+",
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_map_std_types_from_config() {
+    let mut lang = typescript::TypeScript {};
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            config: Config::parse(
+                r#"
+[types]
+"std::path::PathBuf" = { ts = "FilePath", swift = "URL", java = "java.nio.file.Path" }
+"std::time::Duration" = { ts = "number", swift = "TimeInterval", java = "java.time.Duration" }
+"#,
+            )
+            .unwrap(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[typeshare]
+pub struct Job {
+    pub script: PathBuf,
+    pub timeout: std::time::Duration,
+    pub retries: Vec<Duration>,
+    pub log_dir: Option<std::path::PathBuf>,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        "/*
+ Generated by typeshare {}
+*/
+
+export interface Job {{
+	script: FilePath;
+	timeout: number;
+	retries: number[];
+	log_dir?: FilePath | null;
+}}
+
+",
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_type_large_integers_as_bigint() {
+    let mut lang = typescript::TypeScript {};
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            typescript_large_integers: TypeScriptLargeIntegers::BigInt,
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[typeshare]
+#[derive(Serialize, Deserialize)]
+pub struct Counters {
+    pub small: u32,
+    pub signed: i64,
+    pub total: u64,
+    pub huge: i128,
+    pub sizes: Vec<usize>,
+    pub by_id: HashMap<String, u64>,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        "/*
+ Generated by typeshare {}
+*/
+
+export interface Counters {{
+	small: number;
+	signed: bigint;
+	total: bigint;
+	huge: bigint;
+	sizes: bigint[];
+	by_id: Record<string, bigint>;
+}}
+
+",
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+    assert!(g.warnings().is_empty(), "bigint holds every integer");
+}
+
+#[test]
+fn can_type_large_integers_as_strings() {
+    let mut lang = typescript::TypeScript {};
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            typescript_large_integers: TypeScriptLargeIntegers::String,
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[typeshare]
+#[derive(Serialize, Deserialize)]
+pub struct Counters {
+    pub small: u32,
+    pub signed: i64,
+    pub total: u64,
+    pub huge: i128,
+    pub sizes: Vec<usize>,
+    pub by_id: HashMap<String, u64>,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        "/*
+ Generated by typeshare {}
+*/
+
+export function parseBigInt(value: string): bigint {{
+	return BigInt(value);
+}}
+
+export function stringifyBigInt(value: bigint): string {{
+	return value.toString();
+}}
+
+export interface Counters {{
+	small: number;
+	signed: string;
+	total: string;
+	huge: string;
+	sizes: string[];
+	by_id: Record<string, string>;
+}}
+
+",
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn warns_about_large_integers_typed_as_number() {
+    let mut lang = typescript::TypeScript {};
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[typeshare]
+pub struct Counters {
+    pub small: u32,
+    pub by_id: HashMap<u64, Vec<u64>>,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    assert_eq!(g.warnings(), ["Counters.by_id: `u64` is typed as `number` in TypeScript, which rounds values beyond 2^53"]);
+}
+
+#[test]
+fn skips_large_integer_warnings_for_types_given_in_typescript() {
+    let mut lang = typescript::TypeScript {};
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            config: Config::parse(
+                r#"
+[types]
+"ids::Snowflake" = { ts = "string" }
+"ids::Counter" = { swift = "UInt64" }
+"#,
+            )
+            .unwrap(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[typeshare]
+pub struct Counters {
+    #[typeshare(ts = "string")]
+    pub total: u64,
+    #[typeshare(swift = "UInt64")]
+    pub hits: u64,
+    pub by_id: HashMap<String, Snowflake<u64>>,
+    pub last: Counter<u64>,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    assert_eq!(
+        g.warnings(),
+        [
+            "Counters.hits: `u64` is typed as `number` in TypeScript, which rounds values beyond 2^53",
+            "Counters.last: `u64` is typed as `number` in TypeScript, which rounds values beyond 2^53",
+        ]
+    );
+}
+
+#[test]
+fn can_type_map_keys_by_kind() {
+    let mut lang = typescript::TypeScript {};
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[typeshare]
+#[derive(Serialize, Deserialize)]
+pub enum Color {
+    Red,
+    Green,
+}
+
+#[typeshare]
+#[derive(Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct VaultId(u32);
+
+#[typeshare]
+#[derive(Serialize, Deserialize)]
+pub struct Inventory {
+    pub names: HashMap<String, String>,
+    pub by_color: HashMap<Color, u32>,
+    pub by_vault: HashMap<VaultId, Vec<String>>,
+    pub counts: BTreeMap<u16, String>,
+    #[serde(default)]
+    pub nested: Option<Vec<HashMap<Color, bool>>>,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        "/*
+ Generated by typeshare {}
+*/
+
+export type VaultId = number;
+
+export interface Inventory {{
+	names: Record<string, string>;
+	by_color: Partial<Record<Color, number>>;
+	by_vault: Record<VaultId, string[]>;
+	counts: Record<number, string>;
+	nested?: Partial<Record<Color, boolean>>[] | null;
+}}
+
+export enum Color {{
+	Red = \"Red\",
+	Green = \"Green\",
+}}
+
+",
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_override_types_with_serialized_as() {
+    let mut lang = typescript::TypeScript {};
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+/// A password, written as a plain string.
+#[typeshare(serialized_as = "String")]
+pub struct Secret {
+    bytes: Vec<u8>,
+}
+
+#[typeshare(serialized_as = "u8")]
+pub enum Level {
+    Low,
+    High(u8),
+}
+
+#[typeshare(ts = "bigint", swift = "Decimal", java = "java.math.BigDecimal")]
+pub struct Amount {
+    pub units: u64,
+}
+
+#[typeshare]
+#[derive(Serialize, Deserialize)]
+pub struct Login {
+    pub password: Secret,
+    pub history: Vec<Secret>,
+    pub by_site: HashMap<String, Option<Secret>>,
+    pub levels: Vec<Level>,
+    pub balance: Amount,
+    pub amounts: Vec<Amount>,
+    #[typeshare(serialized_as = "String")]
+    pub token: Token,
+    #[typeshare(serialized_as = "Vec<String>")]
+    pub scopes: Scopes,
+    #[typeshare(ts = "`${number}px`", swift = "String", java = "String")]
+    pub width: Option<Width>,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        "/*
+ Generated by typeshare {}
+*/
+
+// A password, written as a plain string.
+export type Secret = string;
+
+export type Level = number;
+
+export interface Login {{
+	password: Secret;
+	history: Secret[];
+	by_site: Record<string, Secret | null>;
+	levels: Level[];
+	balance: bigint;
+	amounts: bigint[];
+	token: string;
+	scopes: string[];
+	width?: `${{number}}px` | null;
+}}
+
+",
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if expected != result {
+        text_diff::print_diff(&expected, &result, " ");
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn can_leave_out_types_overridden_in_the_language() {
+    let mut lang = typescript::TypeScript {};
+    let mut g = Generator::new(
+        &mut lang,
+        Params {
+            use_marker: false,
+            swift_prefix: "".to_string(),
+            java_package: "".to_string(),
+            ..Default::default()
+        },
+    );
+
+    let source = r##"
+#[typeshare(ts = "string", swift = "String")]
+pub struct Width(u64, u64);
+
+#[typeshare(ts = "`${number}px` | `${number}%`")]
+pub enum Size {
+    Pixels(u32),
+    Percent(f32),
+}
+
+pub struct Layout {
+    pub width: Width,
+    pub size: Size,
+}
+"##;
+
+    let mut out: Vec<u8> = Vec::new();
+    assert!(g.process_source(source.to_string(), &mut out).is_ok(), "must be able to process the source");
+    assert!(g.warnings().is_empty(), "Width isn't written in TypeScript");
+    let result = String::from_utf8(out).unwrap();
+
+    let expected = format!(
+        "/*
+ Generated by typeshare {}
+*/
+
+export interface Layout {{
+	width: string;
+	size: `${{number}}px` | `${{number}}%`;
+}}
 
 ",
         env!("CARGO_PKG_VERSION")